@@ -0,0 +1,171 @@
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Answers known to be correct for one year, stored as `answers/<year>.toml`:
+///
+/// ```toml
+/// [day1]
+/// part1 = "68787"
+/// part2 = "198041"
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Answers {
+    answers: BTreeMap<(u8, u8), String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String },
+    Unknown,
+}
+
+#[derive(Debug)]
+pub enum AnswersError {
+    Io(PathBuf, io::Error),
+    Toml(PathBuf, toml::de::Error),
+    /// A table or key that isn't `day<n>` or `part<n>`.
+    Key(PathBuf, String),
+}
+
+impl fmt::Display for AnswersError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AnswersError::Io(path, error) => write!(f, "{}: {}", path.display(), error),
+            AnswersError::Toml(path, error) => write!(f, "{}: {}", path.display(), error),
+            AnswersError::Key(path, key) => write!(
+                f,
+                "{}: expected keys like `day1` and `part1`, found `{}`",
+                path.display(),
+                key
+            ),
+        }
+    }
+}
+
+impl Error for AnswersError {}
+
+fn parse_key(key: &str, prefix: &str) -> Option<u8> {
+    key.strip_prefix(prefix)?.parse().ok()
+}
+
+impl Answers {
+    pub fn path(answers_dir: &Path, year: u16) -> PathBuf {
+        answers_dir.join(format!("{}.toml", year))
+    }
+
+    /// Loads the answers at `path`, treating a missing file as having no answers yet.
+    pub fn load(path: &Path) -> Result<Answers, AnswersError> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Answers::default()),
+            Err(error) => return Err(AnswersError::Io(path.to_owned(), error)),
+        };
+        let tables: BTreeMap<String, BTreeMap<String, String>> = toml::from_str(&contents)
+            .map_err(|error| AnswersError::Toml(path.to_owned(), error))?;
+
+        let mut answers = Answers::default();
+        for (day_key, parts) in tables {
+            let day = parse_key(&day_key, "day")
+                .ok_or_else(|| AnswersError::Key(path.to_owned(), day_key.clone()))?;
+            for (part_key, answer) in parts {
+                let part = parse_key(&part_key, "part")
+                    .ok_or_else(|| AnswersError::Key(path.to_owned(), part_key.clone()))?;
+                answers.record(day, part, answer);
+            }
+        }
+        Ok(answers)
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, self.to_toml())
+    }
+
+    /// Days and parts in numeric order, so that day 10 comes after day 9.
+    pub fn to_toml(&self) -> String {
+        let mut toml = String::new();
+        let mut current_day = None;
+        for (&(day, part), answer) in &self.answers {
+            if current_day != Some(day) {
+                if current_day.is_some() {
+                    toml.push('\n');
+                }
+                toml.push_str(&format!("[day{}]\n", day));
+                current_day = Some(day);
+            }
+            toml.push_str(&format!(
+                "part{} = {}\n",
+                part,
+                toml::Value::String(answer.clone())
+            ));
+        }
+        toml
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&str> {
+        self.answers.get(&(day, part)).map(String::as_str)
+    }
+
+    pub fn record(&mut self, day: u8, part: u8, answer: String) {
+        self.answers.insert((day, part), answer);
+    }
+
+    pub fn check(&self, day: u8, part: u8, answer: &str) -> Verdict {
+        match self.get(day, part) {
+            Some(expected) if expected == answer => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected: expected.to_owned(),
+            },
+            None => Verdict::Unknown,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let mut answers = Answers::default();
+        answers.record(10, 1, "10".to_owned());
+        answers.record(2, 2, "MCD".to_owned());
+        answers.record(2, 1, "CMZ".to_owned());
+        let toml = answers.to_toml();
+        assert_eq!(
+            toml,
+            "[day2]\npart1 = \"CMZ\"\npart2 = \"MCD\"\n\n[day10]\npart1 = \"10\"\n"
+        );
+
+        let dir = std::env::temp_dir().join(format!("aoc-answers-{}", std::process::id()));
+        let path = Answers::path(&dir, 2022);
+        answers.save(&path).unwrap();
+        assert_eq!(Answers::load(&path).unwrap(), answers);
+        fs::remove_dir_all(dir).unwrap();
+    }
+    #[test]
+    fn test_missing_file_is_empty() {
+        let answers = Answers::load(Path::new("answers/does-not-exist.toml")).unwrap();
+        assert_eq!(answers, Answers::default());
+    }
+    #[test]
+    fn test_check() {
+        let mut answers = Answers::default();
+        answers.record(1, 1, "24000".to_owned());
+        assert_eq!(answers.check(1, 1, "24000"), Verdict::Pass);
+        assert_eq!(
+            answers.check(1, 1, "45000"),
+            Verdict::Fail {
+                expected: "24000".to_owned()
+            }
+        );
+        assert_eq!(answers.check(1, 2, "45000"), Verdict::Unknown);
+    }
+}
@@ -0,0 +1,175 @@
+use std::time::Duration;
+
+use serde::{Serialize, Serializer};
+
+use crate::{error::ParseError, solution::Part};
+
+/// Spread of the times taken over every iteration of a benchmark.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct Stats {
+    #[serde(rename = "min_ns", serialize_with = "as_nanos")]
+    pub min: Duration,
+    #[serde(rename = "median_ns", serialize_with = "as_nanos")]
+    pub median: Duration,
+    #[serde(rename = "max_ns", serialize_with = "as_nanos")]
+    pub max: Duration,
+}
+
+impl Stats {
+    fn from_samples(mut samples: Vec<Duration>) -> Stats {
+        samples.sort();
+        Stats {
+            min: samples[0],
+            median: samples[samples.len() / 2],
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+fn as_nanos<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u128(duration.as_nanos())
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct BenchResult {
+    pub day: u8,
+    pub part: u8,
+    pub iterations: usize,
+    pub parse: Stats,
+    pub solve: Stats,
+}
+
+/// Runs one part `iterations` times, timing the parse and solve phases separately.
+pub fn bench(
+    day: u8,
+    part_number: u8,
+    part: &dyn Part,
+    input: &str,
+    iterations: usize,
+) -> Result<BenchResult, ParseError> {
+    assert!(iterations > 0, "Need at least one iteration to time");
+    let mut parse_times = Vec::with_capacity(iterations);
+    let mut solve_times = Vec::with_capacity(iterations);
+    for _ in 0..iterations {
+        let run = part.run_timed(input)?;
+        parse_times.push(run.parse_time);
+        solve_times.push(run.solve_time);
+    }
+    Ok(BenchResult {
+        day,
+        part: part_number,
+        iterations,
+        parse: Stats::from_samples(parse_times),
+        solve: Stats::from_samples(solve_times),
+    })
+}
+
+pub fn render_table(results: &[BenchResult]) -> String {
+    let header = [
+        "day",
+        "part",
+        "parse min",
+        "parse median",
+        "parse max",
+        "solve min",
+        "solve median",
+        "solve max",
+    ];
+    let rows: Vec<Vec<String>> = results
+        .iter()
+        .map(|result| {
+            let mut row = vec![result.day.to_string(), result.part.to_string()];
+            for stats in [result.parse, result.solve] {
+                for time in [stats.min, stats.median, stats.max] {
+                    row.push(format!("{:.2?}", time));
+                }
+            }
+            row
+        })
+        .collect();
+    let widths: Vec<usize> = (0..header.len())
+        .map(|i| {
+            rows.iter()
+                .map(|row| row[i].chars().count())
+                .chain([header[i].len()])
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+
+    let mut table = String::new();
+    let lines = [header.map(String::from).to_vec()].into_iter().chain(rows);
+    for line in lines {
+        let cells: Vec<String> = line
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:>width$}", cell, width = width))
+            .collect();
+        table.push_str(&cells.join("  "));
+        table.push('\n');
+    }
+    table
+}
+
+pub fn render_json(results: &[BenchResult]) -> String {
+    serde_json::to_string_pretty(results).expect("Results are always serialisable")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result() -> BenchResult {
+        BenchResult {
+            day: 1,
+            part: 2,
+            iterations: 3,
+            parse: Stats::from_samples(vec![
+                Duration::from_micros(30),
+                Duration::from_micros(10),
+                Duration::from_micros(20),
+            ]),
+            solve: Stats::from_samples(vec![Duration::from_nanos(5)]),
+        }
+    }
+
+    #[test]
+    fn test_stats() {
+        assert_eq!(
+            result().parse,
+            Stats {
+                min: Duration::from_micros(10),
+                median: Duration::from_micros(20),
+                max: Duration::from_micros(30),
+            }
+        );
+    }
+    #[test]
+    fn test_bench() {
+        let result = bench(1, 1, &crate::day1::Part1, "1000\n\n2000", 4).unwrap();
+        assert_eq!((result.day, result.part, result.iterations), (1, 1, 4));
+        assert!(result.parse.min <= result.parse.median && result.parse.median <= result.parse.max);
+    }
+    #[test]
+    fn test_render_table() {
+        assert_eq!(
+            render_table(&[result()]),
+            "day  part  parse min  parse median  parse max  solve min  solve median  solve max\n\
+             \x20 1     2    10.00µs       20.00µs    30.00µs     5.00ns        5.00ns     5.00ns\n"
+        );
+    }
+    #[test]
+    fn test_render_json() {
+        let json: serde_json::Value = serde_json::from_str(&render_json(&[result()])).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!([{
+                "day": 1,
+                "part": 2,
+                "iterations": 3,
+                "parse": {"min_ns": 10000, "median_ns": 20000, "max_ns": 30000},
+                "solve": {"min_ns": 5, "median_ns": 5, "max_ns": 5},
+            }])
+        );
+    }
+}
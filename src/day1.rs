@@ -1,31 +1,43 @@
-use std::fs;
+use crate::{error::ParseError, solution::Solution};
 
-type Elf = Vec<u32>;
+pub type Elf = Vec<u32>;
 
-pub fn input_generator(input: &str) -> Vec<Elf> {
-    input
-        .split("\n\n")
-        .map(|one_elf| {
-            one_elf
-                .lines()
-                .map(|foodstuff| foodstuff.parse().unwrap())
-                .collect()
-        })
-        .collect()
+pub fn input_generator(input: &str) -> Result<Vec<Elf>, ParseError> {
+    let mut elves = vec![Elf::new()];
+    for (i, foodstuff) in input.lines().enumerate() {
+        if foodstuff.is_empty() {
+            elves.push(Elf::new());
+            continue;
+        }
+        let calories = foodstuff.parse().map_err(|_| {
+            ParseError::at(
+                1,
+                i + 1,
+                foodstuff,
+                foodstuff,
+                "expected a number of calories",
+            )
+        })?;
+        elves
+            .last_mut()
+            .expect("Always starts with one elf")
+            .push(calories);
+    }
+    Ok(elves)
 }
 
-pub fn solve_part1(input: &Vec<Elf>) -> u32 {
+pub fn solve_part1(input: &[Elf]) -> u32 {
     input
-        .into_iter()
-        .map(|one_elf| one_elf.into_iter().sum())
+        .iter()
+        .map(|one_elf| one_elf.iter().sum())
         .max()
         .unwrap()
 }
 
-pub fn solve_part2(input: &Vec<Elf>) -> u32 {
+pub fn solve_part2(input: &[Elf]) -> u32 {
     input
-        .into_iter()
-        .map(|one_elf| one_elf.into_iter().sum())
+        .iter()
+        .map(|one_elf| one_elf.iter().sum())
         .fold([0, 0, 0], |mut acc, t| {
             let current_min: u32 = acc.into_iter().min().unwrap();
             if t > current_min {
@@ -39,30 +51,55 @@ pub fn solve_part2(input: &Vec<Elf>) -> u32 {
         .sum()
 }
 
-pub fn main() {
-    let contents =
-        fs::read_to_string("input/2022/day1.txt").expect("Should have been able to read the file");
-    let input = input_generator(contents.as_str());
-    let part1_answer = solve_part1(&input);
-    let part2_answer = solve_part2(&input);
-    println!("Part 1:\n\n{}\n\n\n", part1_answer);
-    println!("Part 2:\n\n{}\n\n\n", part2_answer);
+pub struct Part1;
+
+impl Solution for Part1 {
+    type Input = Vec<Elf>;
+    type Answer = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input_generator(input)
+    }
+    fn solve(input: &Self::Input) -> Self::Answer {
+        solve_part1(input)
+    }
+}
+
+pub struct Part2;
+
+impl Solution for Part2 {
+    type Input = Vec<Elf>;
+    type Answer = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input_generator(input)
+    }
+    fn solve(input: &Self::Input) -> Self::Answer {
+        solve_part2(input)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const EXAMPLE: &str = include_str!("../examples/2022/day1/example1.txt");
+
     #[test]
     fn part1_example1() {
-        let input =
-            input_generator("1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000");
+        let input = input_generator(EXAMPLE).unwrap();
         assert_eq!(solve_part1(&input), 24000);
     }
     #[test]
     fn part2_example1() {
-        let input =
-            input_generator("1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000");
+        let input = input_generator(EXAMPLE).unwrap();
         assert_eq!(solve_part2(&input), 45000);
     }
+    #[test]
+    fn test_input_generator_error() {
+        let error = input_generator("1000\n2000\n\n30o0").unwrap_err();
+        assert_eq!(error.line, 4);
+        assert_eq!(error.column, 1);
+        assert_eq!(error.text, "30o0");
+    }
 }
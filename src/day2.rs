@@ -1,66 +1,87 @@
-use std::fs;
+use crate::{error::ParseError, solution::Solution};
 
 #[derive(Copy, Clone, Debug, PartialEq)]
-enum Move {
+pub enum Move {
     Rock,
     Paper,
     Scissors,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq)]
-enum Outcome {
+pub enum Outcome {
     Win,
     Draw,
     Lose,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq)]
-struct Match {
-    their_move: Move,
-    our_move: Move,
+pub struct Match {
+    pub their_move: Move,
+    pub our_move: Move,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq)]
-struct MatchWithOutcome {
-    their_move: Move,
-    outcome: Outcome,
+pub struct MatchWithOutcome {
+    pub their_move: Move,
+    pub outcome: Outcome,
 }
 
-fn input_generator_part_1(input: &str) -> Vec<Match> {
+/// Splits a line into the two columns of the strategy guide.
+fn split_columns(line_number: usize, line: &str) -> Result<(&str, &str), ParseError> {
+    let mut columns = line.split_whitespace();
+    match (columns.next(), columns.next(), columns.next()) {
+        (Some(first), Some(second), None) => Ok((first, second)),
+        (_, _, Some(extra)) => Err(ParseError::at(
+            2,
+            line_number,
+            line,
+            extra,
+            "expected only two columns",
+        )),
+        _ => Err(ParseError::at_end(
+            2,
+            line_number,
+            line,
+            "expected two columns",
+        )),
+    }
+}
+
+pub fn input_generator_part_1(input: &str) -> Result<Vec<Match>, ParseError> {
     input
         .lines()
-        .map(|m| {
-            let line: Vec<Move> = m
-                .split_whitespace()
-                .map(|mov| {
-                    if mov == "A" || mov == "X" {
-                        Move::Rock
-                    } else if mov == "B" || mov == "Y" {
-                        Move::Paper
-                    } else if mov == "C" || mov == "Z" {
-                        Move::Scissors
-                    } else {
-                        unreachable!()
-                    }
-                })
-                .collect();
-            Match {
-                their_move: line[0],
-                our_move: line[1],
-            }
+        .enumerate()
+        .map(|(i, m)| {
+            let (their_move, our_move) = split_columns(i + 1, m)?;
+            let parse_move = |mov: &str| {
+                if mov == "A" || mov == "X" {
+                    Ok(Move::Rock)
+                } else if mov == "B" || mov == "Y" {
+                    Ok(Move::Paper)
+                } else if mov == "C" || mov == "Z" {
+                    Ok(Move::Scissors)
+                } else {
+                    Err(ParseError::at(2, i + 1, m, mov, "expected a move"))
+                }
+            };
+            Ok(Match {
+                their_move: parse_move(their_move)?,
+                our_move: parse_move(our_move)?,
+            })
         })
         .collect()
 }
 
-fn input_generator_part_2(input: &str) -> Vec<MatchWithOutcome> {
+pub fn input_generator_part_2(input: &str) -> Result<Vec<MatchWithOutcome>, ParseError> {
     input
         .lines()
-        .map(|m| {
-            let line: Vec<&str> = m.split_whitespace().take(2).collect();
+        .enumerate()
+        .map(|(i, m)| {
+            let (their_move, outcome) = split_columns(i + 1, m)?;
 
-            MatchWithOutcome {
+            Ok(MatchWithOutcome {
                 their_move: {
-                    let mov = line[0];
+                    let mov = their_move;
                     if mov == "A" {
                         Move::Rock
                     } else if mov == "B" {
@@ -68,11 +89,11 @@ fn input_generator_part_2(input: &str) -> Vec<MatchWithOutcome> {
                     } else if mov == "C" {
                         Move::Scissors
                     } else {
-                        panic!("Invalid Char")
+                        return Err(ParseError::at(2, i + 1, m, mov, "expected A, B or C"));
                     }
                 },
                 outcome: {
-                    let mov = line[1];
+                    let mov = outcome;
                     if mov == "X" {
                         Outcome::Lose
                     } else if mov == "Y" {
@@ -80,15 +101,15 @@ fn input_generator_part_2(input: &str) -> Vec<MatchWithOutcome> {
                     } else if mov == "Z" {
                         Outcome::Win
                     } else {
-                        panic!("Invalid Char")
+                        return Err(ParseError::at(2, i + 1, m, mov, "expected X, Y or Z"));
                     }
                 },
-            }
+            })
         })
         .collect()
 }
 
-fn solve_part1(input: &[Match]) -> u32 {
+pub fn solve_part1(input: &[Match]) -> u32 {
     let mut score = 0;
     for m in input {
         score += match m.our_move {
@@ -118,7 +139,7 @@ fn solve_part1(input: &[Match]) -> u32 {
     score
 }
 
-fn solve_part2(input: &[MatchWithOutcome]) -> u32 {
+pub fn solve_part2(input: &[MatchWithOutcome]) -> u32 {
     let mut score = 0;
     for m in input {
         let our_move = match m.their_move {
@@ -165,24 +186,43 @@ fn solve_part2(input: &[MatchWithOutcome]) -> u32 {
     score
 }
 
-pub fn main() {
-    let contents =
-        fs::read_to_string("input/2022/day2.txt").expect("Should have been able to read the file");
-    let input_part_1 = input_generator_part_1(contents.as_str());
-    let input_part_2 = input_generator_part_2(contents.as_str());
-    let part1_answer = solve_part1(&input_part_1);
-    println!("Part 1:\n\n{}\n\n\n", part1_answer);
-    let part2_answer = solve_part2(&input_part_2);
-    println!("Part 2:\n\n{}\n\n\n", part2_answer);
+pub struct Part1;
+
+impl Solution for Part1 {
+    type Input = Vec<Match>;
+    type Answer = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input_generator_part_1(input)
+    }
+    fn solve(input: &Self::Input) -> Self::Answer {
+        solve_part1(input)
+    }
+}
+
+pub struct Part2;
+
+impl Solution for Part2 {
+    type Input = Vec<MatchWithOutcome>;
+    type Answer = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input_generator_part_2(input)
+    }
+    fn solve(input: &Self::Input) -> Self::Answer {
+        solve_part2(input)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const EXAMPLE: &str = include_str!("../examples/2022/day2/example1.txt");
+
     #[test]
     fn example1() {
-        let input = input_generator_part_1("A Y\nB X\nC Z");
+        let input = input_generator_part_1(EXAMPLE).unwrap();
         assert_eq!(
             input,
             [
@@ -203,13 +243,13 @@ mod tests {
     }
     #[test]
     fn example2() {
-        let input = input_generator_part_1("A Y\nB X\nC Z");
+        let input = input_generator_part_1(EXAMPLE).unwrap();
         let output = solve_part1(&input);
         assert_eq!(output, 15);
     }
     #[test]
     fn example3() {
-        let input = input_generator_part_2("A Y\nB X\nC Z");
+        let input = input_generator_part_2(EXAMPLE).unwrap();
         assert_eq!(
             input,
             [
@@ -230,8 +270,17 @@ mod tests {
     }
     #[test]
     fn example4() {
-        let input = input_generator_part_2("A Y\nB X\nC Z");
+        let input = input_generator_part_2(EXAMPLE).unwrap();
         let output = solve_part2(&input);
         assert_eq!(output, 12);
     }
+    #[test]
+    fn test_input_generator_errors() {
+        let error = input_generator_part_1("A Y\nB Q\nC Z").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+        let error = input_generator_part_2("A Y\nB\nC Z").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+        let error = input_generator_part_2("A Y\nB X\nC X Z").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 5));
+    }
 }
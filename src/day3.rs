@@ -1,28 +1,30 @@
 use lazy_static::lazy_static;
-use std::{
-    collections::{HashMap, HashSet},
-    fs,
-};
-type Compartment = Vec<char>;
+use std::collections::{HashMap, HashSet};
+
+use crate::{error::ParseError, solution::Solution};
+
+pub type Compartment = Vec<char>;
 
 #[derive(Clone, Debug, PartialEq)]
-struct Rucksack {
-    compartment1: Compartment,
-    compartment2: Compartment,
+pub struct Rucksack {
+    pub compartment1: Compartment,
+    pub compartment2: Compartment,
 }
 
 impl Rucksack {
-    fn get_overlapping_char(&self) -> char {
+    /// The item in both compartments, if there is one.
+    pub fn find_overlapping_char(&self) -> Option<char> {
         let c1: HashSet<char> = self.compartment1.iter().cloned().collect();
         let c2: HashSet<char> = self.compartment2.iter().cloned().collect();
-        c1.intersection(&c2)
-            .into_iter()
-            .nth(0)
+        c1.intersection(&c2).next().copied()
+    }
+
+    pub fn get_overlapping_char(&self) -> char {
+        self.find_overlapping_char()
             .expect("Problem definition states that there will always be exactly one.")
-            .clone()
     }
 
-    fn new(line: &str) -> Rucksack {
+    pub fn new(line: &str) -> Rucksack {
         let l = line.len();
         let compartment_length = l / 2;
         Rucksack {
@@ -33,7 +35,7 @@ impl Rucksack {
 }
 
 lazy_static! {
-    static ref scores: HashMap<char, u32> = HashMap::from([
+    static ref SCORES: HashMap<char, u32> = HashMap::from([
         ('a', 1),
         ('b', 2),
         ('c', 3),
@@ -90,14 +92,15 @@ lazy_static! {
 }
 
 #[derive(Clone, Debug, PartialEq)]
-struct Group {
-    elf1: Rucksack,
-    elf2: Rucksack,
-    elf3: Rucksack,
+pub struct Group {
+    pub elf1: Rucksack,
+    pub elf2: Rucksack,
+    pub elf3: Rucksack,
 }
 
 impl Group {
-    fn get_common_char(&self) -> char {
+    /// The item all three elves carry, if there is one.
+    pub fn find_common_char(&self) -> Option<char> {
         [&self.elf1, &self.elf2, &self.elf3]
             .into_iter()
             .map(|rs| {
@@ -111,67 +114,142 @@ impl Group {
             })
             .expect("Empty list")
             .into_iter()
-            .nth(0)
+            .next()
+            .copied()
+    }
+
+    pub fn get_common_char(&self) -> char {
+        self.find_common_char()
             .expect("Problem definition states that there will always be exactly one.")
-            .clone()
     }
 
-    fn get_badge_priority(&self) -> u32 {
+    pub fn get_badge_priority(&self) -> u32 {
         let c = self.get_common_char();
-        scores[&c]
+        SCORES[&c]
     }
 }
 
-fn input_generator_part_1(input: &str) -> Vec<Rucksack> {
-    input.lines().map(Rucksack::new).collect()
+pub fn input_generator_part_1(input: &str) -> Result<Vec<Rucksack>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            if let Some((offset, _)) = line.char_indices().find(|(_, c)| !SCORES.contains_key(c)) {
+                return Err(ParseError::at(
+                    3,
+                    i + 1,
+                    line,
+                    &line[offset..],
+                    "expected an item between a and Z",
+                ));
+            }
+            if line.len() % 2 != 0 {
+                return Err(ParseError::at_end(
+                    3,
+                    i + 1,
+                    line,
+                    "expected the same number of items in both compartments",
+                ));
+            }
+            let rucksack = Rucksack::new(line);
+            if rucksack.find_overlapping_char().is_none() {
+                return Err(ParseError::new(
+                    3,
+                    i + 1,
+                    1,
+                    line,
+                    "expected an item in both compartments",
+                ));
+            }
+            Ok(rucksack)
+        })
+        .collect()
 }
 
-fn input_generator_part_2(input: &str) -> Vec<Group> {
-    input_generator_part_1(input)
+pub fn input_generator_part_2(input: &str) -> Result<Vec<Group>, ParseError> {
+    let rucksacks = input_generator_part_1(input)?;
+    if rucksacks.len() % 3 != 0 {
+        return Err(ParseError::new(
+            3,
+            rucksacks.len() + 1,
+            1,
+            "",
+            "expected the rucksacks to come in groups of three",
+        ));
+    }
+    let groups: Vec<Group> = rucksacks
         .chunks_exact(3)
         .map(|chunk| Group {
             elf1: chunk[0].to_owned(),
             elf2: chunk[1].to_owned(),
             elf3: chunk[2].to_owned(),
         })
-        .collect()
+        .collect();
+    if let Some(i) = groups
+        .iter()
+        .position(|group| group.find_common_char().is_none())
+    {
+        let first_line = i * 3 + 1;
+        return Err(ParseError::new(
+            3,
+            first_line,
+            1,
+            input.lines().nth(first_line - 1).unwrap_or_default(),
+            "expected an item all three rucksacks in this group have",
+        ));
+    }
+    Ok(groups)
 }
 
-fn solve_part1(input: &[Rucksack]) -> u32 {
+pub fn solve_part1(input: &[Rucksack]) -> u32 {
     input
-        .into_iter()
+        .iter()
         .map(|rucksack| rucksack.get_overlapping_char())
-        .map(|c| scores[&c])
+        .map(|c| SCORES[&c])
         .sum()
 }
 
-fn solve_part2(input: &[Group]) -> u32 {
-    input
-        .into_iter()
-        .map(|x| x.get_badge_priority())
-        .sum::<u32>()
+pub fn solve_part2(input: &[Group]) -> u32 {
+    input.iter().map(|x| x.get_badge_priority()).sum::<u32>()
+}
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    type Input = Vec<Rucksack>;
+    type Answer = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input_generator_part_1(input)
+    }
+    fn solve(input: &Self::Input) -> Self::Answer {
+        solve_part1(input)
+    }
 }
 
-pub fn main() {
-    let contents =
-        fs::read_to_string("input/2022/day3.txt").expect("Should have been able to read the file");
-    let input_part_1 = input_generator_part_1(contents.as_str());
-    let input_part_2 = input_generator_part_2(contents.as_str());
-    let part1_answer = solve_part1(&input_part_1);
-    println!("Part 1:\n\n{}\n\n\n", part1_answer);
-    let part2_answer = solve_part2(&input_part_2);
-    println!("Part 2:\n\n{}\n\n\n", part2_answer);
+pub struct Part2;
+
+impl Solution for Part2 {
+    type Input = Vec<Group>;
+    type Answer = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input_generator_part_2(input)
+    }
+    fn solve(input: &Self::Input) -> Self::Answer {
+        solve_part2(input)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const EXAMPLE: &str = include_str!("../examples/2022/day3/example1.txt");
+
     #[test]
     fn test_input_generator() {
-        let input = input_generator_part_1(
-            "vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\nPmmdzqPrVvPwwTWBwg\nwMqvLMZHhHMvwLHjbvcjnnSBnvTQFn\nttgJtRGJQctTZtZT\nCrZsJsPPZsGzwwsLwLmpwMDw"
-            );
+        let input = input_generator_part_1(EXAMPLE).unwrap();
         assert_eq!(
             input,
             [
@@ -204,13 +282,13 @@ mod tests {
     }
     #[test]
     fn test_part_1_solver() {
-        let input = input_generator_part_1("vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\nPmmdzqPrVvPwwTWBwg\nwMqvLMZHhHMvwLHjbvcjnnSBnvTQFn\nttgJtRGJQctTZtZT\nCrZsJsPPZsGzwwsLwLmpwMDw");
+        let input = input_generator_part_1(EXAMPLE).unwrap();
         let output = solve_part1(&input);
         assert_eq!(output, 157);
     }
     #[test]
     fn test_part_2_generator() {
-        let input = input_generator_part_2("vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\nPmmdzqPrVvPwwTWBwg\nwMqvLMZHhHMvwLHjbvcjnnSBnvTQFn\nttgJtRGJQctTZtZT\nCrZsJsPPZsGzwwsLwLmpwMDw");
+        let input = input_generator_part_2(EXAMPLE).unwrap();
         assert_eq!(
             input,
             [
@@ -305,8 +383,31 @@ mod tests {
 
     #[test]
     fn test_part_2_solver() {
-        let input = input_generator_part_2("vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\nPmmdzqPrVvPwwTWBwg\nwMqvLMZHhHMvwLHjbvcjnnSBnvTQFn\nttgJtRGJQctTZtZT\nCrZsJsPPZsGzwwsLwLmpwMDw");
+        let input = input_generator_part_2(EXAMPLE).unwrap();
         let output = solve_part2(&input);
         assert_eq!(output, 70);
     }
+
+    #[test]
+    fn test_input_generator_errors() {
+        let error =
+            input_generator_part_1("vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNq RjqzjGDLGLrsFMfFZSrLrFZsSL")
+                .unwrap_err();
+        assert_eq!((error.line, error.column), (2, 7));
+        let error = input_generator_part_1("vJrwpWtwJgWrhcsFMMfFFhFpp").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 26));
+        let error = input_generator_part_2("vJrwpWtwJgWrhcsFMMfFFhFp").unwrap_err();
+        assert_eq!(error.line, 2);
+        let error = input_generator_part_1("vJrwpWtwJgWrhcsFMMfFFhFp\nab").unwrap_err();
+        assert_eq!(
+            (error.line, error.message.as_str()),
+            (2, "expected an item in both compartments")
+        );
+        let error = input_generator_part_2(&format!("{}aAbA\ncCdC\neEfE\n", EXAMPLE)).unwrap_err();
+        assert_eq!(error.line, 7);
+        assert_eq!(
+            error.message,
+            "expected an item all three rucksacks in this group have"
+        );
+    }
 }
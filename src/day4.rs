@@ -1,109 +1,126 @@
-use std::fs;
+use crate::{error::ParseError, solution::Solution};
 
 #[derive(Clone, Copy, Debug, PartialEq)]
-struct PLACEMARKER {}
-
-#[derive(Clone, Copy, Debug, PartialEq)]
-struct Elf {
-    start_id: u32,
-    end_id: u32,
+pub struct Elf {
+    pub start_id: u32,
+    pub end_id: u32,
 }
 
 impl Elf {
-    fn is_within(&self, other: Elf) -> bool {
-        if other.start_id <= self.start_id && other.end_id >= self.end_id {
-            true
-        } else {
-            false
-        }
+    pub fn is_within(&self, other: Elf) -> bool {
+        other.start_id <= self.start_id && other.end_id >= self.end_id
     }
 
-    fn overlaps(&self, other: Elf) -> bool {
+    pub fn overlaps(&self, other: Elf) -> bool {
         (self.start_id >= other.start_id && self.start_id <= other.end_id)
             || (self.end_id >= other.start_id && self.end_id <= other.end_id)
     }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
-struct ElfPair {
-    elf1: Elf,
-    elf2: Elf,
+pub struct ElfPair {
+    pub elf1: Elf,
+    pub elf2: Elf,
 }
 
 impl ElfPair {
-    fn containing(&self) -> bool {
+    pub fn containing(&self) -> bool {
         self.elf1.is_within(self.elf2) || self.elf2.is_within(self.elf1)
     }
-    fn overlapping(&self) -> bool {
+    pub fn overlapping(&self) -> bool {
         self.containing() || self.elf1.overlaps(self.elf2)
     }
 }
 
-fn input_generator_part_1(input: &str) -> Vec<ElfPair> {
+fn parse_elf(line_number: usize, line: &str, range: &str) -> Result<Elf, ParseError> {
+    let (start_id, end_id) = range
+        .split_once('-')
+        .ok_or_else(|| ParseError::at(4, line_number, line, range, "expected a range like 2-4"))?;
+    let parse_id = |id: &str| {
+        id.parse::<u32>()
+            .map_err(|_| ParseError::at(4, line_number, line, id, "expected a section id"))
+    };
+    Ok(Elf {
+        start_id: parse_id(start_id)?,
+        end_id: parse_id(end_id)?,
+    })
+}
+
+pub fn input_generator_part_1(input: &str) -> Result<Vec<ElfPair>, ParseError> {
     input
         .lines()
-        .map(|input| {
-            let elves: Vec<Elf> = input
-                .split(",")
-                .map(|input| {
-                    let range: Vec<u32> = input
-                        .split("-")
-                        .map(|id| id.parse::<u32>().expect("Problem defines input as numbers"))
-                        .collect();
-                    Elf {
-                        start_id: range[0],
-                        end_id: range[1],
-                    }
-                })
-                .collect();
-            ElfPair {
-                elf1: elves[0],
-                elf2: elves[1],
-            }
+        .enumerate()
+        .map(|(i, input)| {
+            let (elf1, elf2) = input.split_once(',').ok_or_else(|| {
+                ParseError::at_end(4, i + 1, input, "expected a comma between the two elves")
+            })?;
+            Ok(ElfPair {
+                elf1: parse_elf(i + 1, input, elf1)?,
+                elf2: parse_elf(i + 1, input, elf2)?,
+            })
         })
         .collect()
 }
 
-fn input_generator_part_2(input: &str) -> Vec<ElfPair> {
+pub fn input_generator_part_2(input: &str) -> Result<Vec<ElfPair>, ParseError> {
     input_generator_part_1(input)
 }
 
-fn solve_part1(input: &[ElfPair]) -> u32 {
+pub fn solve_part1(input: &[ElfPair]) -> u32 {
     input
-        .into_iter()
+        .iter()
         .filter(|pair| pair.containing())
         .count()
         .try_into()
         .unwrap()
 }
 
-fn solve_part2(input: &[ElfPair]) -> u32 {
+pub fn solve_part2(input: &[ElfPair]) -> u32 {
     input
-        .into_iter()
+        .iter()
         .filter(|pair| pair.overlapping())
         .count()
         .try_into()
         .unwrap()
 }
 
-pub fn main() {
-    let contents =
-        fs::read_to_string("input/2022/day4.txt").expect("Should have been able to read the file");
-    let input_part_1 = input_generator_part_1(contents.as_str());
-    let input_part_2 = input_generator_part_2(contents.as_str());
-    let part1_answer = solve_part1(&input_part_1);
-    println!("Part 1:\n\n{}\n\n\n", part1_answer);
-    let part2_answer = solve_part2(&input_part_2);
-    println!("Part 2:\n\n{}\n\n\n", part2_answer);
+pub struct Part1;
+
+impl Solution for Part1 {
+    type Input = Vec<ElfPair>;
+    type Answer = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input_generator_part_1(input)
+    }
+    fn solve(input: &Self::Input) -> Self::Answer {
+        solve_part1(input)
+    }
+}
+
+pub struct Part2;
+
+impl Solution for Part2 {
+    type Input = Vec<ElfPair>;
+    type Answer = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input_generator_part_2(input)
+    }
+    fn solve(input: &Self::Input) -> Self::Answer {
+        solve_part2(input)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const EXAMPLE: &str = include_str!("../examples/2022/day4/example1.txt");
+
     #[test]
     fn test_input_generator() {
-        let input = input_generator_part_1("2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8");
+        let input = input_generator_part_1(EXAMPLE).unwrap();
         assert_eq!(
             input,
             [
@@ -172,20 +189,30 @@ mod tests {
     }
     #[test]
     fn test_part_1_solver() {
-        let input = input_generator_part_1("2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8");
+        let input = input_generator_part_1(EXAMPLE).unwrap();
         let output = solve_part1(&input);
         assert_eq!(output, 2);
     }
     //#[test]
     //fn test_part_2_generator() {
-    //let input = input_generator_part_2("2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8");
+    //let input = input_generator_part_2(EXAMPLE);
     //assert_eq!(input, []);
     //}
 
     #[test]
     fn test_part_2_solver() {
-        let input = input_generator_part_2("2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8");
+        let input = input_generator_part_2(EXAMPLE).unwrap();
         let output = solve_part2(&input);
         assert_eq!(output, 4);
     }
+
+    #[test]
+    fn test_input_generator_errors() {
+        let error = input_generator_part_1("2-4,6-8\n2-3;4-5").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 8));
+        let error = input_generator_part_1("2-4,6-x").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 7));
+        let error = input_generator_part_1("2-4,68").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 5));
+    }
 }
@@ -1,166 +1,680 @@
-use std::{
-    collections::{HashMap, VecDeque},
-    fs,
-};
+use std::{cmp::Ordering, collections::HashMap, error::Error, fmt, num::NonZeroU32, str::FromStr};
 
 use itertools::Itertools;
 
+use crate::{error::ParseError, solution::Solution};
+
 #[derive(Clone, Debug, PartialEq)]
-struct Ship {
-    stacks: HashMap<usize, Vec<char>>,
+pub struct Ship {
+    /// Each stack's crate labels, from the bottom up.
+    pub stacks: HashMap<usize, Vec<String>>,
 }
 impl Ship {
-    fn execute_instruction_set(&mut self, inset: InstructionSet) {
-        for ins in inset.instructions {
-            self.execute_ins(ins)
+    /// A ship with the given stacks, numbered from 1, each listed from the bottom up.
+    pub fn from_stacks<S: Into<String>>(stacks: Vec<Vec<S>>) -> Ship {
+        Ship {
+            stacks: (1..)
+                .zip(stacks)
+                .map(|(id, stack)| (id, stack.into_iter().map(Into::into).collect()))
+                .collect(),
         }
     }
-    fn execute_instruction_set_on_9001(&mut self, inset: InstructionSet) {
-        for ins in inset.instructions {
-            self.execute_ins_on_9001(ins)
-        }
+    /// Carries out every instruction, panicking at the first one that can't be carried out.
+    pub fn execute(&mut self, inset: &InstructionSet, crane: &mut dyn Crane) {
+        self.try_execute(inset, crane)
+            .unwrap_or_else(|error| panic!("{}", error))
     }
-    fn execute_ins(&mut self, ins: Instruction) {
-        let from = self.stacks.get_mut(&ins.from).expect("Should exist");
-        let mut tmp: VecDeque<char> = VecDeque::new();
-        for _ in 0..ins.number {
-            tmp.push_back(from.pop().expect("Should not remove from an empty stack"))
-        }
-        let to = self.stacks.get_mut(&ins.to).expect("Should exist");
-        for _ in 0..ins.number {
-            to.push(
-                tmp.pop_front()
-                    .expect("Should not remove from an empty stack"),
-            )
-        }
+    /// Carries out the instructions up to the first one that can't be carried out, leaving the
+    /// ship as it was just before that one.
+    pub fn try_execute(
+        &mut self,
+        inset: &InstructionSet,
+        crane: &mut dyn Crane,
+    ) -> Result<(), InstructionError> {
+        self.execute_traced(inset, crane, |_, _, _| {})
     }
-    fn execute_ins_on_9001(&mut self, ins: Instruction) {
-        let from = self.stacks.get_mut(&ins.from).expect("Should exist");
-        let mut tmp: Vec<char> = Vec::new();
-        for _ in 0..ins.number {
-            tmp.push(from.pop().expect("Should not remove from an empty stack"))
+    /// Like [`Ship::try_execute`], calling `step` after each instruction with its index and the
+    /// ship as it is then.
+    pub fn execute_traced(
+        &mut self,
+        inset: &InstructionSet,
+        crane: &mut dyn Crane,
+        mut step: impl FnMut(usize, Instruction, &Ship),
+    ) -> Result<(), InstructionError> {
+        for (index, &instruction) in inset.instructions.iter().enumerate() {
+            self.validate(instruction)
+                .map_err(|problem| InstructionError {
+                    index,
+                    instruction,
+                    problem,
+                })?;
+            crane.execute(self, instruction);
+            step(index, instruction, self)
         }
-        let to = self.stacks.get_mut(&ins.to).expect("Should exist");
-        for _ in 0..ins.number {
-            to.push(tmp.pop().expect("Should not remove from an empty stack"))
+        Ok(())
+    }
+    /// Whether the ship can carry out one instruction as it stands.
+    pub fn validate(&self, ins: Instruction) -> Result<(), InstructionProblem> {
+        validate_heights(|id| self.stacks.get(&id).map(Vec::len), ins)
+    }
+    /// Checks every instruction can be carried out, without moving anything. Every crane moves
+    /// the same number of crates between the same stacks, so this only keeps count of how tall
+    /// each stack is, and the answer is the same for all of them.
+    pub fn check(&self, inset: &InstructionSet) -> Result<(), InstructionError> {
+        let mut heights: HashMap<usize, usize> = self
+            .stacks
+            .iter()
+            .map(|(&id, stack)| (id, stack.len()))
+            .collect();
+        for (index, &instruction) in inset.instructions.iter().enumerate() {
+            validate_heights(|id| heights.get(&id).copied(), instruction).map_err(|problem| {
+                InstructionError {
+                    index,
+                    instruction,
+                    problem,
+                }
+            })?;
+            let number = instruction.number as usize;
+            *heights.get_mut(&instruction.from).expect("Validated") -= number;
+            *heights.get_mut(&instruction.to).expect("Validated") += number;
         }
+        Ok(())
+    }
+    /// Takes the top `number` crates off a stack, in the order they were stacked.
+    pub fn lift(&mut self, from: usize, number: u32) -> Vec<String> {
+        let from = self.stacks.get_mut(&from).expect("Should exist");
+        let remaining = from
+            .len()
+            .checked_sub(number as usize)
+            .expect("Should not remove from an empty stack");
+        from.split_off(remaining)
+    }
+    /// Puts crates on top of a stack, the first one lowest.
+    pub fn put(&mut self, to: usize, crates: Vec<String>) {
+        self.stacks
+            .get_mut(&to)
+            .expect("Should exist")
+            .extend(crates)
     }
-    fn get_tops(&self) -> String {
+    pub fn get_tops(&self) -> String {
         self.stacks
             .keys()
             .sorted()
             .filter_map(|id| self.stacks[id].last())
+            .map(String::as_str)
             .collect()
     }
 
-    fn new(input: &str) -> Ship {
+    /// Reads a drawing like the puzzle's. The row of stack numbers at the bottom says where each
+    /// stack is, so the numbers can be any width and needn't be evenly spaced, and a crate belongs
+    /// to the one stack whose number is under some part of it. Crate labels can be more than one
+    /// character long, like `[AB]`, and lines can have their trailing spaces trimmed.
+    pub fn new(input: &str) -> Result<Ship, ParseError> {
         let mut ship = Ship {
             stacks: HashMap::new(),
         };
 
-        for line in input.rsplit('\n').skip(1) {
-            for container in line.chars().chunks(4).into_iter().enumerate() {
-                let c = container.1.collect_vec()[1];
-                if c != ' ' {
-                    ship.stacks.entry(container.0 + 1).or_default().push(c)
+        let lines = input.lines().collect_vec();
+        let Some((base, rows)) = lines.split_last() else {
+            return Err(ParseError::at_end(
+                5,
+                1,
+                input,
+                "expected a drawing of the stacks",
+            ));
+        };
+        // Each stack, and the columns its number takes up. Stacks numbered in the base row exist
+        // even if they have no crates on them.
+        let mut columns = vec![];
+        for number in base.split_whitespace() {
+            let error = |message: String| ParseError::at(5, lines.len(), base, number, message);
+            let id = number
+                .parse()
+                .ok()
+                .filter(|&id| id > 0)
+                .ok_or_else(|| error("expected a stack number".to_owned()))?;
+            if ship.stacks.insert(id, vec![]).is_some() {
+                return Err(error(format!("stack {} is numbered twice", id)));
+            }
+            let start = column(base, number);
+            columns.push((id, start..start + number.chars().count()));
+        }
+        if columns.is_empty() {
+            return Err(ParseError::at_end(
+                5,
+                lines.len(),
+                base,
+                "expected a row of stack numbers",
+            ));
+        }
+
+        for (i, line) in rows.iter().enumerate().rev() {
+            let height = rows.len() - 1 - i;
+            let mut rest = line.trim_start();
+            while !rest.is_empty() {
+                let error =
+                    |token: &str, message: String| ParseError::at(5, i + 1, line, token, message);
+                let Some(inner) = rest.strip_prefix('[') else {
+                    let token = &rest[..rest.chars().next().map_or(0, char::len_utf8)];
+                    return Err(error(
+                        token,
+                        "expected a crate like [A] or a gap".to_owned(),
+                    ));
+                };
+                let Some(close) = inner.find(']') else {
+                    return Err(ParseError::at_end(5, i + 1, line, "expected a `]`"));
+                };
+                let label = &inner[..close];
+                let token = &rest[..close + 2];
+                if label.is_empty() || label.contains(|c: char| c == '[' || c.is_whitespace()) {
+                    return Err(error(token, "expected a crate label like A".to_owned()));
                 }
+
+                let start = column(line, token);
+                let span = start..start + token.chars().count();
+                let under = columns
+                    .iter()
+                    .filter(|(_, number)| number.start < span.end && span.start < number.end)
+                    .map(|&(id, _)| id)
+                    .collect_vec();
+                let id = match under[..] {
+                    [id] => id,
+                    [] => {
+                        return Err(error(
+                            token,
+                            "expected a crate above a stack number".to_owned(),
+                        ))
+                    }
+                    _ => {
+                        return Err(error(
+                            token,
+                            format!(
+                                "expected a crate above one stack, not {}",
+                                under.iter().join(" and ")
+                            ),
+                        ))
+                    }
+                };
+                let stack = ship.stacks.get_mut(&id).expect("Should exist");
+                match stack.len().cmp(&height) {
+                    Ordering::Less => {
+                        return Err(error(
+                            token,
+                            format!("expected a crate below this one in stack {}", id),
+                        ))
+                    }
+                    Ordering::Greater => {
+                        return Err(error(
+                            token,
+                            format!("expected one crate in stack {} on this line", id),
+                        ))
+                    }
+                    Ordering::Equal => stack.push(label.to_owned()),
+                }
+                rest = rest[token.len()..].trim_start();
             }
         }
 
-        ship
+        Ok(ship)
+    }
+}
+
+/// Whether an instruction can be carried out on stacks with the given heights, or `None` for
+/// stacks that don't exist.
+fn validate_heights(
+    height: impl Fn(usize) -> Option<usize>,
+    ins: Instruction,
+) -> Result<(), InstructionProblem> {
+    let available = height(ins.from).ok_or(InstructionProblem::NoSuchStack { stack: ins.from })?;
+    if height(ins.to).is_none() {
+        return Err(InstructionProblem::NoSuchStack { stack: ins.to });
+    }
+    if available < ins.number as usize {
+        return Err(InstructionProblem::NotEnoughCrates {
+            stack: ins.from,
+            needed: ins.number,
+            available,
+        });
+    }
+    Ok(())
+}
+
+/// The 0-based character column where `part`, a slice of `line`, starts.
+fn column(line: &str, part: &str) -> usize {
+    line[..part.as_ptr() as usize - line.as_ptr() as usize]
+        .chars()
+        .count()
+}
+
+/// How a crane model carries out instructions. Cranes can keep state between instructions.
+pub trait Crane {
+    fn execute(&mut self, ship: &mut Ship, ins: Instruction);
+}
+
+/// Moves crates one at a time, so a move reverses their order.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CrateMover9000;
+
+impl Crane for CrateMover9000 {
+    fn execute(&mut self, ship: &mut Ship, ins: Instruction) {
+        let mut crates = ship.lift(ins.from, ins.number);
+        crates.reverse();
+        ship.put(ins.to, crates)
+    }
+}
+
+/// Moves all the crates at once, keeping their order.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CrateMover9001;
+
+impl Crane for CrateMover9001 {
+    fn execute(&mut self, ship: &mut Ship, ins: Instruction) {
+        let crates = ship.lift(ins.from, ins.number);
+        ship.put(ins.to, crates)
+    }
+}
+
+/// Like the CrateMover 9001, but can only lift `capacity` crates at a time, so big moves are
+/// split into several lifts from the top down.
+#[derive(Clone, Copy, Debug)]
+pub struct LimitedCrane {
+    pub capacity: NonZeroU32,
+}
+
+impl Crane for LimitedCrane {
+    fn execute(&mut self, ship: &mut Ship, ins: Instruction) {
+        let mut remaining = ins.number;
+        while remaining > 0 {
+            let lift = remaining.min(self.capacity.get());
+            let crates = ship.lift(ins.from, lift);
+            ship.put(ins.to, crates);
+            remaining -= lift;
+        }
+    }
+}
+
+/// Moves all the crates at once, but flips the load over on every other move, starting with the
+/// first.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FlippingCrane {
+    moves: usize,
+}
+
+impl Crane for FlippingCrane {
+    fn execute(&mut self, ship: &mut Ship, ins: Instruction) {
+        let mut crates = ship.lift(ins.from, ins.number);
+        if self.moves.is_multiple_of(2) {
+            crates.reverse();
+        }
+        self.moves += 1;
+        ship.put(ins.to, crates)
+    }
+}
+
+/// The crane models that can be picked by name, such as from the command line.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CraneModel {
+    CrateMover9000,
+    CrateMover9001,
+    Limited(NonZeroU32),
+    Flipping,
+}
+
+impl CraneModel {
+    pub fn crane(self) -> Box<dyn Crane> {
+        match self {
+            CraneModel::CrateMover9000 => Box::new(CrateMover9000),
+            CraneModel::CrateMover9001 => Box::new(CrateMover9001),
+            CraneModel::Limited(capacity) => Box::new(LimitedCrane { capacity }),
+            CraneModel::Flipping => Box::new(FlippingCrane::default()),
+        }
+    }
+}
+
+/// Parses `9000`, `9001`, `limited:<capacity>` or `flipping`.
+impl FromStr for CraneModel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<CraneModel, String> {
+        match s {
+            "9000" => Ok(CraneModel::CrateMover9000),
+            "9001" => Ok(CraneModel::CrateMover9001),
+            "flipping" => Ok(CraneModel::Flipping),
+            _ => match s.strip_prefix("limited:").map(str::parse) {
+                Some(Ok(capacity)) => Ok(CraneModel::Limited(capacity)),
+                Some(_) => Err("expected a capacity of at least 1, like `limited:2`".to_owned()),
+                None => {
+                    Err("expected `9000`, `9001`, `limited:<capacity>` or `flipping`".to_owned())
+                }
+            },
+        }
+    }
+}
+
+/// Draws the ship as the puzzle does, with every line padded to the full width and a row of stack
+/// numbers at the bottom, so that [`Ship::new`] reads back the same ship. Only the stacks the ship
+/// has are drawn, in order of their numbers. Every column is as wide as the widest crate or
+/// number, with each crate and number centred in it.
+impl fmt::Display for Ship {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn centre(text: &str, width: usize) -> String {
+            let padding = width - text.chars().count();
+            format!(
+                "{}{}{}",
+                " ".repeat(padding / 2),
+                text,
+                " ".repeat(padding - padding / 2)
+            )
+        }
+
+        let ids = self.stacks.keys().copied().sorted().collect_vec();
+        let height = self.stacks.values().map(Vec::len).max().unwrap_or(0);
+        let label_width = self
+            .stacks
+            .values()
+            .flatten()
+            .map(|label| label.chars().count())
+            .max()
+            .unwrap_or(1);
+        let number_width = ids.last().map_or(1, |id| id.to_string().len());
+        let width = (label_width + 2).max(number_width);
+        for row in (0..height).rev() {
+            let line = ids
+                .iter()
+                .map(|id| match self.stacks[id].get(row) {
+                    Some(label) => centre(&format!("[{}]", label), width),
+                    None => " ".repeat(width),
+                })
+                .join(" ");
+            writeln!(f, "{}", line)?;
+        }
+        writeln!(
+            f,
+            "{}",
+            ids.iter()
+                .map(|id| centre(&id.to_string(), width))
+                .join(" ")
+        )
     }
 }
 
 #[derive(Clone, Debug, PartialEq)]
-struct InstructionSet {
-    instructions: Vec<Instruction>,
+pub struct InstructionSet {
+    pub instructions: Vec<Instruction>,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
-struct Instruction {
-    number: u32,
-    from: usize,
-    to: usize,
+pub struct Instruction {
+    pub number: u32,
+    pub from: usize,
+    pub to: usize,
 }
 
-fn input_generator_part_1(input: &str) -> (Ship, InstructionSet) {
-    let mut input = input.split("\n\n");
-    let ship_layout = input.next().unwrap();
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "move {} from {} to {}", self.number, self.from, self.to)
+    }
+}
+
+/// Why an instruction can't be carried out.
+#[derive(Clone, Debug, PartialEq)]
+pub enum InstructionProblem {
+    NoSuchStack {
+        stack: usize,
+    },
+    NotEnoughCrates {
+        stack: usize,
+        needed: u32,
+        available: usize,
+    },
+}
+
+impl InstructionProblem {
+    /// How many crates short the stack is, if that's the problem.
+    pub fn shortfall(&self) -> Option<usize> {
+        match *self {
+            InstructionProblem::NoSuchStack { .. } => None,
+            InstructionProblem::NotEnoughCrates {
+                needed, available, ..
+            } => Some(needed as usize - available),
+        }
+    }
+}
+
+impl fmt::Display for InstructionProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            InstructionProblem::NoSuchStack { stack } => write!(f, "there is no stack {}", stack),
+            InstructionProblem::NotEnoughCrates {
+                stack,
+                needed,
+                available,
+            } => write!(
+                f,
+                "stack {} has {} crate{}, {} short of {}",
+                stack,
+                available,
+                if available == 1 { "" } else { "s" },
+                needed as usize - available,
+                needed
+            ),
+        }
+    }
+}
+
+/// An instruction that can't be carried out, and its 0-based index in the instruction set.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InstructionError {
+    pub index: usize,
+    pub instruction: Instruction,
+    pub problem: InstructionProblem,
+}
+
+impl fmt::Display for InstructionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "instruction {} ({}): {}",
+            self.index + 1,
+            self.instruction,
+            self.problem
+        )
+    }
+}
+
+impl Error for InstructionError {}
+
+fn parse_instruction(line_number: usize, line: &str) -> Result<Instruction, ParseError> {
+    fn number<T: FromStr>(line_number: usize, line: &str, word: &str) -> Result<T, ParseError> {
+        word.parse()
+            .map_err(|_| ParseError::at(5, line_number, line, word, "expected a number"))
+    }
+
+    let mut parts = line.split(' ');
+    let mut field = |keyword: &str| match parts.next() {
+        Some(word) if word == keyword => parts
+            .next()
+            .ok_or_else(|| ParseError::at_end(5, line_number, line, "expected a number")),
+        Some(word) => Err(ParseError::at(
+            5,
+            line_number,
+            line,
+            word,
+            format!("expected `{}`", keyword),
+        )),
+        None => Err(ParseError::at_end(
+            5,
+            line_number,
+            line,
+            format!("expected `{}`", keyword),
+        )),
+    };
+    let number_word = field("move")?;
+    let from_word = field("from")?;
+    let to_word = field("to")?;
+    if let Some(extra) = parts.next() {
+        return Err(ParseError::at(
+            5,
+            line_number,
+            line,
+            extra,
+            "expected the end of the instruction",
+        ));
+    }
+    Ok(Instruction {
+        number: number(line_number, line, number_word)?,
+        from: number(line_number, line, from_word)?,
+        to: number(line_number, line, to_word)?,
+    })
+}
+
+pub fn input_generator_part_1(input: &str) -> Result<(Ship, InstructionSet), ParseError> {
+    let (ship_layout, instructions) = input.split_once("\n\n").ok_or_else(|| {
+        ParseError::new(
+            5,
+            input.lines().count() + 1,
+            1,
+            "",
+            "expected a blank line between the drawing and the instructions",
+        )
+    })?;
+    let first_instruction_line = ship_layout.lines().count() + 2;
 
-    let instructions = input.next().unwrap().split('\n');
     let instruction_set = InstructionSet {
         instructions: instructions
-            .filter(|x| !x.is_empty())
-            .map(|line| {
-                let mut parts = line.split(' ');
-                let number = parts
-                    .nth(1)
-                    .expect("There should be enough items in the line")
-                    .parse()
-                    .expect("Should be parsable");
-                let from = parts
-                    .nth(1)
-                    .expect("There should be enough items in the line")
-                    .parse()
-                    .expect("Should be parsable");
-                let to = parts
-                    .nth(1)
-                    .expect("There should be enough items in the line")
-                    .parse()
-                    .expect("Should be parsable");
-                Instruction { number, from, to }
-            })
-            .collect(),
+            .lines()
+            .enumerate()
+            .filter(|(_, x)| !x.is_empty())
+            .map(|(i, line)| parse_instruction(first_instruction_line + i, line))
+            .collect::<Result<_, _>>()?,
     };
-    (Ship::new(ship_layout), instruction_set)
+    Ok((Ship::new(ship_layout)?, instruction_set))
 }
 
-fn input_generator_part_2(input: &str) -> (Ship, InstructionSet) {
+pub fn input_generator_part_2(input: &str) -> Result<(Ship, InstructionSet), ParseError> {
     input_generator_part_1(input)
 }
 
-fn solve_part1(input: (Ship, InstructionSet)) -> String {
-    let mut ship = input.0;
-    let instructions = input.1;
-    ship.execute_instruction_set(instructions);
-    ship.get_tops()
+/// The crates on top of each stack once `crane` has carried out every instruction.
+pub fn try_solve_with(
+    input: &(Ship, InstructionSet),
+    crane: &mut dyn Crane,
+) -> Result<String, InstructionError> {
+    let (ship, instructions) = input;
+    let mut ship = ship.clone();
+    ship.try_execute(instructions, crane)?;
+    Ok(ship.get_tops())
+}
+
+/// Like [`try_solve_with`], panicking if an instruction can't be carried out.
+pub fn solve_with(input: &(Ship, InstructionSet), crane: &mut dyn Crane) -> String {
+    try_solve_with(input, crane).unwrap_or_else(|error| panic!("{}", error))
+}
+
+/// The starting drawing, then each instruction followed by the drawing after it.
+pub fn render_trace(
+    input: &(Ship, InstructionSet),
+    crane: &mut dyn Crane,
+) -> Result<String, InstructionError> {
+    let (ship, instructions) = input;
+    let mut trace = format!("{}\n", ship);
+    ship.clone()
+        .execute_traced(instructions, crane, |_, ins, ship| {
+            trace.push_str(&format!("{}\n{}\n", ins, ship))
+        })?;
+    Ok(trace)
+}
+
+pub fn solve_part1(input: &(Ship, InstructionSet)) -> String {
+    solve_with(input, &mut CrateMover9000)
+}
+
+pub fn solve_part2(input: &(Ship, InstructionSet)) -> String {
+    solve_with(input, &mut CrateMover9001)
+}
+
+/// Rejects a parsed input if [`Ship::check`] finds an instruction that can't be carried out,
+/// pointing at the number of crates or the stack in that instruction's line.
+///
+/// Both parts run this as part of parsing, so their parse times, as reported by `aoc bench` and
+/// `aoc run --format`, include it. It only counts crates, without running a crane, so it takes
+/// far less time than solving.
+pub fn check_input(
+    input: &str,
+    parsed: (Ship, InstructionSet),
+) -> Result<(Ship, InstructionSet), ParseError> {
+    let (ship, instructions) = &parsed;
+    let Err(error) = ship.check(instructions) else {
+        return Ok(parsed);
+    };
+    let (layout, _) = input
+        .split_once("\n\n")
+        .expect("A parsed input has a blank line after the drawing");
+    let (i, line) = input
+        .lines()
+        .enumerate()
+        .skip(layout.lines().count() + 1)
+        .filter(|(_, line)| !line.is_empty())
+        .nth(error.index)
+        .expect("Every instruction has a line");
+    // The words are `move <number> from <from> to <to>`
+    let words = line.split(' ').collect_vec();
+    let word = match error.problem {
+        InstructionProblem::NotEnoughCrates { .. } => words[1],
+        InstructionProblem::NoSuchStack { stack } if stack == error.instruction.from => words[3],
+        InstructionProblem::NoSuchStack { .. } => words[5],
+    };
+    Err(ParseError::at(
+        5,
+        i + 1,
+        line,
+        word,
+        error.problem.to_string(),
+    ))
 }
 
-fn solve_part2(input: (Ship, InstructionSet)) -> String {
-    let mut ship = input.0;
-    let instructions = input.1;
-    ship.execute_instruction_set_on_9001(instructions);
-    ship.get_tops()
+pub struct Part1;
+
+impl Solution for Part1 {
+    type Input = (Ship, InstructionSet);
+    type Answer = String;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        check_input(input, input_generator_part_1(input)?)
+    }
+    fn solve(input: &Self::Input) -> Self::Answer {
+        solve_part1(input)
+    }
 }
 
-pub fn main() {
-    let contents =
-        fs::read_to_string("input/2022/day5.txt").expect("Should have been able to read the file");
-    let input_part_1 = input_generator_part_1(contents.as_str());
-    let input_part_2 = input_generator_part_2(contents.as_str());
-    let part1_answer = solve_part1(input_part_1);
-    println!("Part 1:\n\n{}\n\n\n", part1_answer);
-    let part2_answer = solve_part2(input_part_2);
-    println!("Part 2:\n\n{}\n\n\n", part2_answer);
+pub struct Part2;
+
+impl Solution for Part2 {
+    type Input = (Ship, InstructionSet);
+    type Answer = String;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        check_input(input, input_generator_part_2(input)?)
+    }
+    fn solve(input: &Self::Input) -> Self::Answer {
+        solve_part2(input)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = include_str!("../examples/2022/day5/example1.txt");
 
     #[test]
     fn test_input_generator() {
-        let input = input_generator_part_1("    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n\nmove 1 from 2 to 1\nmove 3 from 1 to 3\nmove 2 from 2 to 1\nmove 1 from 1 to 2");
+        let input = input_generator_part_1(EXAMPLE).unwrap();
         assert_eq!(
             input,
             (
-                Ship {
-                    stacks: HashMap::from([
-                        (1, vec!['Z', 'N']),
-                        (2, vec!['M', 'C', 'D']),
-                        (3, vec!['P'])
-                    ])
-                },
+                Ship::from_stacks(vec![vec!['Z', 'N'], vec!['M', 'C', 'D'], vec!['P']]),
                 InstructionSet {
                     instructions: vec![
                         Instruction {
@@ -190,8 +704,8 @@ mod tests {
     }
     #[test]
     fn test_part_1_solver() {
-        let input = input_generator_part_1("    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n\nmove 1 from 2 to 1\nmove 3 from 1 to 3\nmove 2 from 2 to 1\nmove 1 from 1 to 2");
-        let output = solve_part1(input);
+        let input = input_generator_part_1(EXAMPLE).unwrap();
+        let output = solve_part1(&input);
         assert_eq!(output, "CMZ");
     }
     //#[test]
@@ -202,8 +716,275 @@ mod tests {
 
     #[test]
     fn test_part_2_solver() {
-        let input = input_generator_part_2("    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n\nmove 1 from 2 to 1\nmove 3 from 1 to 3\nmove 2 from 2 to 1\nmove 1 from 1 to 2");
-        let output = solve_part2(input);
+        let input = input_generator_part_2(EXAMPLE).unwrap();
+        let output = solve_part2(&input);
         assert_eq!(output, "MCD");
     }
+
+    #[test]
+    fn test_input_generator_errors() {
+        let error = input_generator_part_1("    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n\nmove 1 from 2 to 1\nmove 3 form 1 to 3").unwrap_err();
+        assert_eq!((error.line, error.column), (7, 8));
+        let error = input_generator_part_1(
+            "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n\nmove x from 2 to 1",
+        )
+        .unwrap_err();
+        assert_eq!((error.line, error.column), (6, 6));
+        let error = input_generator_part_1(
+            "    [D]    \n[N] (C)    \n[Z] [M] [P]\n 1   2   3 \n\nmove 1 from 2 to 1",
+        )
+        .unwrap_err();
+        assert_eq!((error.line, error.column), (2, 5));
+        let error = input_generator_part_1(
+            "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \nmove 1 from 2 to 1",
+        )
+        .unwrap_err();
+        assert_eq!(error.line, 6);
+    }
+    #[test]
+    fn test_cranes() {
+        let input = input_generator_part_1(EXAMPLE).unwrap();
+        let mut limited = CraneModel::Limited(NonZeroU32::new(2).unwrap()).crane();
+        assert_eq!(solve_with(&input, limited.as_mut()), "MCZ");
+        assert_eq!(
+            solve_with(
+                &input,
+                &mut LimitedCrane {
+                    capacity: NonZeroU32::MIN
+                }
+            ),
+            "CMZ"
+        );
+        assert_eq!(solve_with(&input, &mut FlippingCrane::default()), "CMD");
+    }
+    #[test]
+    fn test_limited_crane() {
+        let mut ship = Ship::from_stacks(vec![vec!['A', 'B', 'C', 'D', 'E'], vec![]]);
+        LimitedCrane {
+            capacity: NonZeroU32::new(2).unwrap(),
+        }
+        .execute(
+            &mut ship,
+            Instruction {
+                number: 5,
+                from: 1,
+                to: 2,
+            },
+        );
+        assert_eq!(
+            ship,
+            Ship::from_stacks(vec![vec![], vec!['D', 'E', 'B', 'C', 'A']])
+        );
+    }
+    #[test]
+    fn test_crane_model_from_str() {
+        assert_eq!("9000".parse(), Ok(CraneModel::CrateMover9000));
+        assert_eq!("9001".parse(), Ok(CraneModel::CrateMover9001));
+        assert_eq!(
+            "limited:3".parse(),
+            Ok(CraneModel::Limited(NonZeroU32::new(3).unwrap()))
+        );
+        assert_eq!("flipping".parse(), Ok(CraneModel::Flipping));
+        assert!("limited:0".parse::<CraneModel>().is_err());
+        assert!("9002".parse::<CraneModel>().is_err());
+    }
+    #[test]
+    fn test_display() {
+        let (ship, instructions) = input_generator_part_1(EXAMPLE).unwrap();
+        assert_eq!(
+            ship.to_string(),
+            "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n"
+        );
+        assert_eq!(
+            instructions.instructions[0].to_string(),
+            "move 1 from 2 to 1"
+        );
+    }
+    #[test]
+    fn test_render_trace() {
+        let input = input_generator_part_1(EXAMPLE).unwrap();
+        let trace = render_trace(&input, &mut CrateMover9000).unwrap();
+        assert!(trace.starts_with(
+            "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n\n\
+             move 1 from 2 to 1\n[D]        \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n\n\
+             move 3 from 1 to 3\n"
+        ));
+        assert!(trace.ends_with(
+            "move 1 from 1 to 2\n        [Z]\n        [N]\n        [D]\n[C] [M] [P]\n 1   2   3 \n\n"
+        ));
+    }
+    #[test]
+    fn test_empty_stacks() {
+        let ship = Ship::from_stacks(vec![vec![], vec!['A'], vec![]]);
+        let drawing = ship.to_string();
+        assert_eq!(drawing, "    [A]    \n 1   2   3 \n");
+        assert_eq!(Ship::new(&drawing).unwrap(), ship);
+        assert_eq!(
+            Ship::new(" 1   2 ").unwrap(),
+            Ship::from_stacks(vec![Vec::<String>::new(), vec![]])
+        );
+        let error = Ship::new("[A]\n 1   x").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 6));
+    }
+
+    #[test]
+    fn test_ragged_drawing() {
+        assert_eq!(
+            Ship::new("    [D]\n[N] [C]\n[Z] [M] [P]\n 1   2   3").unwrap(),
+            input_generator_part_1(EXAMPLE).unwrap().0
+        );
+    }
+    #[test]
+    fn test_wide_drawing() {
+        let ship = Ship::new("[AB]\n[C]   [DEF]\n 1  2  3    10").unwrap();
+        assert_eq!(ship.stacks[&1], vec!["C", "AB"]);
+        assert_eq!(ship.stacks[&3], vec!["DEF"]);
+        assert_eq!(ship.stacks[&10], Vec::<String>::new());
+        assert_eq!(ship.get_tops(), "ABDEF");
+        assert_eq!(
+            ship.to_string(),
+            "[AB]                   \n [C]        [DEF]      \n  1     2     3    10  \n"
+        );
+        assert_eq!(Ship::new(&ship.to_string()).unwrap(), ship);
+
+        let ship = Ship::from_stacks((1..=10).map(|id| vec![id.to_string()]).collect());
+        assert_eq!(
+            ship.to_string(),
+            "[1]  [2]  [3]  [4]  [5]  [6]  [7]  [8]  [9]  [10]\n\
+             \x201    2    3    4    5    6    7    8    9    10 \n"
+        );
+        assert_eq!(Ship::new(&ship.to_string()).unwrap(), ship);
+    }
+    #[test]
+    fn test_drawing_errors() {
+        let error = |drawing: &str| {
+            let error = Ship::new(drawing).unwrap_err();
+            (error.line, error.column, error.message)
+        };
+        assert_eq!(
+            error("[A]\n 1   x"),
+            (2, 6, "expected a stack number".to_owned())
+        );
+        assert_eq!(
+            error(" 1   1"),
+            (1, 6, "stack 1 is numbered twice".to_owned())
+        );
+        assert_eq!(error("[A\n 1 "), (1, 3, "expected a `]`".to_owned()));
+        assert_eq!(
+            error("[A]\n   "),
+            (2, 4, "expected a row of stack numbers".to_owned())
+        );
+        assert_eq!(
+            error("[]\n 1 "),
+            (1, 1, "expected a crate label like A".to_owned())
+        );
+        assert_eq!(
+            error("    [A]\n 1 "),
+            (1, 5, "expected a crate above a stack number".to_owned())
+        );
+        assert_eq!(
+            error("[ABCDE]\n 1 2"),
+            (
+                1,
+                1,
+                "expected a crate above one stack, not 1 and 2".to_owned()
+            )
+        );
+        assert_eq!(
+            error("[A]\n    [B]\n 1   2 "),
+            (
+                1,
+                1,
+                "expected a crate below this one in stack 1".to_owned()
+            )
+        );
+        assert_eq!(
+            error("[A][B]\n  12"),
+            (
+                1,
+                4,
+                "expected one crate in stack 12 on this line".to_owned()
+            )
+        );
+    }
+
+    #[test]
+    fn test_try_execute() {
+        let (ship, mut instructions) = input_generator_part_1(EXAMPLE).unwrap();
+        let mut moved = ship.clone();
+        assert_eq!(
+            moved.try_execute(&instructions, &mut CrateMover9000),
+            Ok(())
+        );
+        assert_eq!(moved.get_tops(), "CMZ");
+
+        instructions.instructions[2].number = 5;
+        let mut moved = ship.clone();
+        let error = moved
+            .try_execute(&instructions, &mut CrateMover9000)
+            .unwrap_err();
+        assert_eq!(
+            error.problem,
+            InstructionProblem::NotEnoughCrates {
+                stack: 2,
+                needed: 5,
+                available: 2
+            }
+        );
+        assert_eq!(error.index, 2);
+        assert_eq!(error.problem.shortfall(), Some(3));
+        assert_eq!(
+            error.to_string(),
+            "instruction 3 (move 5 from 2 to 1): stack 2 has 2 crates, 3 short of 5"
+        );
+        // Stopped just before the instruction that failed
+        assert_eq!(moved.get_tops(), "CZ");
+        assert_eq!(ship.check(&instructions), Err(error));
+    }
+    #[test]
+    fn test_parts_reject_impossible_instructions() {
+        let day = crate::DAYS.iter().find(|day| day.day == 5).unwrap();
+        let input = "[A]\n 1   2\n\nmove 1 from 1 to 2\n\nmove 2 from 1 to 2\n";
+        for part in day.parts {
+            let error = part.run(input).unwrap_err();
+            assert_eq!((error.line, error.column), (6, 6));
+            assert_eq!(error.message, "stack 1 has 0 crates, 2 short of 2");
+        }
+        let error = day.parts[1]
+            .run("[A]\n 1   2\n\nmove 2 from 1 to 2\n")
+            .unwrap_err();
+        assert_eq!(error.message, "stack 1 has 1 crate, 1 short of 2");
+        let input = EXAMPLE.replace("from 1 to 3", "from 1 to 4");
+        let error = check_input(&input, input_generator_part_1(&input).unwrap()).unwrap_err();
+        assert_eq!((error.line, error.column), (7, 18));
+        assert!(day.parts[0].run(EXAMPLE).is_ok());
+    }
+    #[test]
+    fn test_check() {
+        let (ship, mut instructions) = input_generator_part_1(EXAMPLE).unwrap();
+        assert_eq!(ship.check(&instructions), Ok(()));
+
+        instructions.instructions[3].to = 4;
+        let error = ship.check(&instructions).unwrap_err();
+        assert_eq!(
+            (error.index, error.problem),
+            (3, InstructionProblem::NoSuchStack { stack: 4 })
+        );
+        // Nothing was moved
+        assert_eq!(ship, input_generator_part_1(EXAMPLE).unwrap().0);
+    }
+
+    proptest! {
+        #[test]
+        fn test_drawing_round_trip(
+            stacks in prop::collection::hash_map(
+                1usize..120,
+                prop::collection::vec("[A-Z]{1,3}", 0..8),
+                1..15,
+            )
+        ) {
+            let ship = Ship { stacks };
+            prop_assert_eq!(Ship::new(&ship.to_string()).unwrap(), ship);
+        }
+    }
 }
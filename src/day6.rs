@@ -1,7 +1,9 @@
-use std::{collections::VecDeque, fs};
+use std::collections::VecDeque;
 
 use itertools::{FoldWhile, Itertools};
 
+use crate::{error::ParseError, solution::Solution};
+
 #[derive(Clone, Debug, PartialEq)]
 struct State {
     queue: VecDeque<char>,
@@ -33,7 +35,7 @@ impl State {
     }
 }
 
-fn solve_part1(input: &str) -> u32 {
+pub fn solve_part1(input: &str) -> u32 {
     input
         .chars()
         .fold_while(
@@ -53,7 +55,7 @@ fn solve_part1(input: &str) -> u32 {
         .into_inner()
         .count
 }
-fn solve_part2(input: &str) -> u32 {
+pub fn solve_part2(input: &str) -> u32 {
     input
         .chars()
         .fold_while(
@@ -74,70 +76,95 @@ fn solve_part2(input: &str) -> u32 {
         .count
 }
 
-pub fn main() {
-    let contents =
-        fs::read_to_string("input/2022/day6.txt").expect("Should have been able to read the file");
-    let part1_answer = solve_part1(contents.as_str());
-    println!("Part 1:\n\n{}\n\n\n", part1_answer);
-    let part2_answer = solve_part2(contents.as_str());
-    println!("Part 2:\n\n{}\n\n\n", part2_answer);
+pub struct Part1;
+
+impl Solution for Part1 {
+    type Input = String;
+    type Answer = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok(input.to_owned())
+    }
+    fn solve(input: &Self::Input) -> Self::Answer {
+        solve_part1(input)
+    }
+}
+
+pub struct Part2;
+
+impl Solution for Part2 {
+    type Input = String;
+    type Answer = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok(input.to_owned())
+    }
+    fn solve(input: &Self::Input) -> Self::Answer {
+        solve_part2(input)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const EXAMPLE_A: &str = include_str!("../examples/2022/day6/example_a.txt");
+    const EXAMPLE_B: &str = include_str!("../examples/2022/day6/example_b.txt");
+    const EXAMPLE_C: &str = include_str!("../examples/2022/day6/example_c.txt");
+    const EXAMPLE_D: &str = include_str!("../examples/2022/day6/example_d.txt");
+    const EXAMPLE_E: &str = include_str!("../examples/2022/day6/example_e.txt");
+
     // Part 1
     #[test]
     fn test_part_1_solver_a() {
-        let output = solve_part1("mjqjpqmgbljsphdztnvjfqwrcgsmlb");
+        let output = solve_part1(EXAMPLE_A);
         assert_eq!(output, 7);
     }
     #[test]
     fn test_part_1_solver_b() {
-        let output = solve_part1("bvwbjplbgvbhsrlpgdmjqwftvncz");
+        let output = solve_part1(EXAMPLE_B);
         assert_eq!(output, 5);
     }
     #[test]
     fn test_part_1_solver_c() {
-        let output = solve_part1("nppdvjthqldpwncqszvftbrmjlhg");
+        let output = solve_part1(EXAMPLE_C);
         assert_eq!(output, 6);
     }
     #[test]
     fn test_part_1_solver_d() {
-        let output = solve_part1("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg");
+        let output = solve_part1(EXAMPLE_D);
         assert_eq!(output, 10);
     }
     #[test]
     fn test_part_1_solver_e() {
-        let output = solve_part1("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw");
+        let output = solve_part1(EXAMPLE_E);
         assert_eq!(output, 11);
     }
 
     // Part 2
     #[test]
     fn test_part_2_solver_a() {
-        let output = solve_part2("mjqjpqmgbljsphdztnvjfqwrcgsmlb");
+        let output = solve_part2(EXAMPLE_A);
         assert_eq!(output, 19);
     }
     #[test]
     fn test_part_2_solver_b() {
-        let output = solve_part2("bvwbjplbgvbhsrlpgdmjqwftvncz");
+        let output = solve_part2(EXAMPLE_B);
         assert_eq!(output, 23);
     }
     #[test]
     fn test_part_2_solver_c() {
-        let output = solve_part2("nppdvjthqldpwncqszvftbrmjlhg");
+        let output = solve_part2(EXAMPLE_C);
         assert_eq!(output, 23);
     }
     #[test]
     fn test_part_2_solver_d() {
-        let output = solve_part2("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg");
+        let output = solve_part2(EXAMPLE_D);
         assert_eq!(output, 29);
     }
     #[test]
     fn test_part_2_solver_e() {
-        let output = solve_part2("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw");
+        let output = solve_part2(EXAMPLE_E);
         assert_eq!(output, 26);
     }
 }
@@ -1,150 +1,732 @@
+pub mod check;
+pub mod diff;
+pub mod generate;
+pub mod json;
+pub mod query;
+pub mod tree;
+
 use nom::{
     branch::alt,
-    bytes::complete::{tag, take_while_m_n},
-    combinator::map_res,
-    sequence::tuple,
+    bytes::complete::{tag, take_while1},
+    character::complete::u64 as size,
+    combinator::{cut, eof, map, peek, value, verify},
+    error::{context, VerboseError, VerboseErrorKind},
+    sequence::{preceded, separated_pair, terminated},
     IResult,
 };
-use std::fs;
-struct State<'a>(Vec<&'a Directory>);
+
+use crate::{error::ParseError, solution::Solution};
+
+use query::{Kind, Match, Query, SortKey};
+
+/// Index of a node in a [`FileSystem`].
+pub type NodeId = usize;
 
 #[derive(Clone, Debug, PartialEq)]
-enum DirectoryItem {
+pub enum DirectoryItem {
     File(File),
     Directory(Directory),
 }
 
-impl DirectoryItem {
-    fn get_size(&self) -> u32 {
-        match self {
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Directory {
+    pub children: Vec<NodeId>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct File {
+    pub size: u64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Node {
+    pub name: String,
+    pub parent: Option<NodeId>,
+    pub item: DirectoryItem,
+}
+
+/// A named directory tree, stored as an arena of nodes that refer to each other by index so that
+/// it can be built up while navigating it.
+#[derive(Clone, Debug)]
+pub struct FileSystem {
+    nodes: Vec<Node>,
+}
+
+impl FileSystem {
+    pub const ROOT: NodeId = 0;
+
+    pub fn new() -> FileSystem {
+        FileSystem {
+            nodes: vec![Node {
+                name: "/".to_owned(),
+                parent: None,
+                item: DirectoryItem::Directory(Directory::default()),
+            }],
+        }
+    }
+
+    /// Builds the tree described by a transcript, creating directories that are changed into
+    /// without having been listed and skipping anything contradictory. See [`check::check`] for
+    /// what counts as contradictory.
+    pub fn from_transcript(lines: &[Line]) -> FileSystem {
+        check::check(lines).0
+    }
+
+    pub fn node(&self, id: NodeId) -> &Node {
+        &self.nodes[id]
+    }
+
+    /// The children of a directory, or nothing for a file.
+    pub fn children(&self, id: NodeId) -> &[NodeId] {
+        match &self.nodes[id].item {
+            DirectoryItem::Directory(dir) => &dir.children,
+            DirectoryItem::File(_) => &[],
+        }
+    }
+
+    pub fn is_dir(&self, id: NodeId) -> bool {
+        matches!(self.nodes[id].item, DirectoryItem::Directory(_))
+    }
+
+    pub fn child(&self, dir: NodeId, name: &str) -> Option<NodeId> {
+        self.children(dir)
+            .iter()
+            .copied()
+            .find(|&child| self.nodes[child].name == name)
+    }
+
+    /// Adds `item` to the directory `dir`, unless something called `name` is already there, and
+    /// returns its id either way.
+    pub fn add(&mut self, dir: NodeId, name: &str, item: DirectoryItem) -> NodeId {
+        if let Some(existing) = self.child(dir, name) {
+            return existing;
+        }
+        let id = self.nodes.len();
+        self.nodes.push(Node {
+            name: name.to_owned(),
+            parent: Some(dir),
+            item,
+        });
+        match &mut self.nodes[dir].item {
+            DirectoryItem::Directory(dir) => dir.children.push(id),
+            DirectoryItem::File(_) => panic!("Can only add to a directory"),
+        }
+        id
+    }
+
+    /// Takes `id`, and everything inside it, out of the tree. The nodes stay in the arena, but
+    /// nothing reachable from the root refers to them any more.
+    pub fn remove(&mut self, id: NodeId) {
+        let parent = self.nodes[id].parent.take().expect("Can't remove the root");
+        if let DirectoryItem::Directory(dir) = &mut self.nodes[parent].item {
+            dir.children.retain(|&child| child != id);
+        }
+    }
+
+    /// Where `cd <name>` from `cwd` ends up, if that's a known directory. Like a shell, `cd ..`
+    /// in the root stays there.
+    pub fn cd(&self, cwd: NodeId, name: &str) -> Option<NodeId> {
+        match name {
+            "/" => Some(FileSystem::ROOT),
+            ".." => Some(self.nodes[cwd].parent.unwrap_or(FileSystem::ROOT)),
+            name => self.child(cwd, name).filter(|&child| self.is_dir(child)),
+        }
+    }
+
+    /// Finds an absolute path like `/a/e` or `/a/h.lst`.
+    pub fn lookup(&self, path: &str) -> Option<NodeId> {
+        path.strip_prefix('/')?
+            .split('/')
+            .filter(|name| !name.is_empty())
+            .try_fold(FileSystem::ROOT, |dir, name| self.child(dir, name))
+    }
+
+    /// The absolute path of a node, `/` for the root.
+    pub fn path(&self, id: NodeId) -> String {
+        let mut names = vec![];
+        let mut node = id;
+        while let Some(parent) = self.nodes[node].parent {
+            names.push(self.nodes[node].name.as_str());
+            node = parent;
+        }
+        names.reverse();
+        format!("/{}", names.join("/"))
+    }
+
+    pub fn size(&self, id: NodeId) -> u64 {
+        match &self.nodes[id].item {
             DirectoryItem::File(file) => file.size,
-            DirectoryItem::Directory(directory) => directory.get_size(),
+            DirectoryItem::Directory(dir) => dir.children.iter().map(|&c| self.size(c)).sum(),
         }
     }
-}
 
-#[derive(Clone, Debug, PartialEq)]
-struct Directory(Vec<DirectoryItem>);
+    /// The recursive size of every node, indexed by [`NodeId`], found in one pass from the leaves
+    /// up. This relies on children always being added after their parents.
+    pub fn sizes(&self) -> Vec<u64> {
+        let mut sizes: Vec<u64> = self
+            .nodes
+            .iter()
+            .map(|node| match &node.item {
+                DirectoryItem::File(file) => file.size,
+                DirectoryItem::Directory(_) => 0,
+            })
+            .collect();
+        for id in (0..self.nodes.len()).rev() {
+            if let Some(parent) = self.nodes[id].parent {
+                sizes[parent] += sizes[id];
+            }
+        }
+        sizes
+    }
 
-impl Directory {
-    fn get_size(&self) -> u32 {
-        (&self).0.iter().map(|item| item.get_size()).sum()
+    /// Every node in the tree, each one before the things inside it.
+    pub fn walk(&self) -> Vec<NodeId> {
+        let mut order = vec![];
+        let mut stack = vec![FileSystem::ROOT];
+        while let Some(id) = stack.pop() {
+            order.push(id);
+            stack.extend(self.children(id).iter().rev());
+        }
+        order
     }
 
-    fn push(&mut self, item: DirectoryItem) {
-        self.0.push(item)
+    /// Every directory, as `du` lists them: each one after the directories inside it.
+    pub fn directories_post_order(&self) -> Vec<NodeId> {
+        let mut order = vec![];
+        let mut stack = vec![(FileSystem::ROOT, false)];
+        while let Some((id, visited)) = stack.pop() {
+            if visited {
+                order.push(id);
+            } else if self.is_dir(id) {
+                stack.push((id, true));
+                stack.extend(self.children(id).iter().rev().map(|&child| (child, false)));
+            }
+        }
+        order
+    }
+
+    /// The path and recursive size of every directory, like `du`.
+    pub fn du(&self) -> Vec<(String, u64)> {
+        let sizes = self.sizes();
+        self.directories_post_order()
+            .into_iter()
+            .map(|id| (self.path(id), sizes[id]))
+            .collect()
+    }
+
+    fn same_tree(&self, id: NodeId, other: &FileSystem, other_id: NodeId) -> bool {
+        let (node, other_node) = (&self.nodes[id], &other.nodes[other_id]);
+        if node.name != other_node.name {
+            return false;
+        }
+        match (&node.item, &other_node.item) {
+            (DirectoryItem::File(file), DirectoryItem::File(other_file)) => file == other_file,
+            (DirectoryItem::Directory(dir), DirectoryItem::Directory(other_dir)) => {
+                dir.children.len() == other_dir.children.len()
+                    && dir.children.iter().all(|&child| {
+                        other
+                            .child(other_id, &self.nodes[child].name)
+                            .is_some_and(|other_child| self.same_tree(child, other, other_child))
+                    })
+            }
+            _ => false,
+        }
     }
 }
 
-#[derive(Clone, Debug, PartialEq)]
-struct File {
-    size: u32,
+impl Default for FileSystem {
+    fn default() -> FileSystem {
+        FileSystem::new()
+    }
 }
 
-enum Line<'a> {
+/// Two filesystems are equal when they hold the same tree, whatever order it was built in.
+impl PartialEq for FileSystem {
+    fn eq(&self, other: &FileSystem) -> bool {
+        self.same_tree(FileSystem::ROOT, other, FileSystem::ROOT)
+    }
+}
+
+/// One line of the terminal transcript.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Line<'a> {
     Command(Command<'a>),
-    DirectoryItem(DirectoryItem),
+    /// `dir <name>`, listed by `ls`.
+    Directory(&'a str),
+    /// `<size> <name>`, listed by `ls`.
+    File(u64, &'a str),
 }
 
-enum Command<'a> {
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Command<'a> {
     Ls,
+    /// `cd <name>`, where the name may also be `..` or `/`.
     Cd(&'a str),
+    /// `rm <name>`, which only removes files.
+    Rm(&'a str),
+    /// `rm -r <name>`, which removes a file or a directory and everything in it.
+    RmRecursive(&'a str),
+    Mkdir(&'a str),
+    /// `touch <size> <name>`, creating a file of that size.
+    Touch(u64, &'a str),
+}
+
+type ParseResult<'a, T> = IResult<&'a str, T, VerboseError<&'a str>>;
+
+/// A file or directory name, rejecting words that couldn't be one, like `..` or `a/b`.
+fn name(input: &str) -> ParseResult<'_, &str> {
+    let word = || take_while1(|c: char| !c.is_whitespace());
+    preceded(
+        peek(word()),
+        context(
+            "expected a name without `/` that isn't `.` or `..`",
+            verify(word(), is_valid_name),
+        ),
+    )(input)
 }
 
-fn command(input: &str) -> IResult<&str, Command> {
-    todo!()
+/// Whether a file or directory called `name` could appear in a transcript: it isn't empty, `.` or
+/// `..`, and has no `/` or whitespace in it.
+pub fn is_valid_name(name: &str) -> bool {
+    !matches!(name, "" | "." | "..") && !name.contains(|c: char| c == '/' || c.is_whitespace())
 }
 
-fn directory(input: &str) -> IResult<&str, Directory> {
-    todo!()
+pub fn command(input: &str) -> ParseResult<'_, Command<'_>> {
+    preceded(
+        tag("$ "),
+        cut(context(
+            "expected `ls`, `cd`, `rm`, `mkdir` or `touch`",
+            alt((
+                value(Command::Ls, tag("ls")),
+                map(
+                    preceded(
+                        tag("cd "),
+                        cut(context(
+                            "expected a directory to change to",
+                            alt((terminated(tag(".."), eof), terminated(tag("/"), eof), name)),
+                        )),
+                    ),
+                    Command::Cd,
+                ),
+                map(
+                    preceded(
+                        tag("rm -r "),
+                        cut(context("expected something to remove", name)),
+                    ),
+                    Command::RmRecursive,
+                ),
+                map(
+                    preceded(tag("rm "), cut(context("expected a file to remove", name))),
+                    Command::Rm,
+                ),
+                map(
+                    preceded(
+                        tag("mkdir "),
+                        cut(context("expected the name of the directory", name)),
+                    ),
+                    Command::Mkdir,
+                ),
+                map(
+                    preceded(
+                        tag("touch "),
+                        cut(separated_pair(
+                            context("expected the size of the file", size),
+                            tag(" "),
+                            context("expected the name of the file", name),
+                        )),
+                    ),
+                    |(size, name)| Command::Touch(size, name),
+                ),
+            )),
+        )),
+    )(input)
 }
 
-fn file(input: &str) -> IResult<&str, File> {
-    todo!()
+pub fn directory(input: &str) -> ParseResult<'_, &str> {
+    preceded(
+        tag("dir "),
+        cut(context("expected the name of the directory", name)),
+    )(input)
 }
-fn parseLine(input: &str) -> IResult<&str, Line> {
-    todo!()
+
+pub fn file(input: &str) -> ParseResult<'_, (u64, &str)> {
+    separated_pair(
+        context("expected a command, `dir <name>` or `<size> <name>`", size),
+        tag(" "),
+        cut(context("expected the name of the file", name)),
+    )(input)
 }
 
-fn input_generator_part_1(input: &str) -> Directory {
-    let root = Directory(vec![]);
+pub fn parse_line(input: &str) -> ParseResult<'_, Line<'_>> {
+    terminated(
+        alt((
+            map(command, Line::Command),
+            map(directory, Line::Directory),
+            map(file, |(size, name)| Line::File(size, name)),
+        )),
+        context("expected the end of the line", eof),
+    )(input)
+}
+
+/// Parses every line of the transcript, pointing at the first line that isn't understood.
+pub fn parse_transcript(input: &str) -> Result<Vec<Line<'_>>, ParseError> {
     input
         .lines()
-        .skip(1)
-        .fold(State(vec![&root]), |mut state, line| {
-            dbg!(line);
-            let pwd = state.0.last_mut();
-
-            state
+        .enumerate()
+        .map(|(i, line)| match parse_line(line) {
+            Ok((_, parsed)) => Ok(parsed),
+            Err(nom::Err::Error(error)) | Err(nom::Err::Failure(error)) => {
+                let (at, _) = error.errors[0];
+                let message = error
+                    .errors
+                    .iter()
+                    .find_map(|(_, kind)| match kind {
+                        VerboseErrorKind::Context(message) => Some(*message),
+                        _ => None,
+                    })
+                    .unwrap_or("expected a command or a listing");
+                Err(ParseError::at(7, i + 1, line, at, message))
+            }
+            Err(nom::Err::Incomplete(_)) => unreachable!("Only complete parsers are used"),
         })
-        .0
-        .first()
-        .expect("Will always include at least the root directory")
-        .to_owned()
-        .to_owned()
+        .collect()
+}
+
+/// Builds the filesystem, rejecting transcripts with any of the problems [`check::check`] finds.
+pub fn input_generator_part_1(input: &str) -> Result<FileSystem, ParseError> {
+    let (fs, issues) = check::check(&parse_transcript(input)?);
+    match issues.first() {
+        Some(issue) => {
+            let text = input.lines().nth(issue.line - 1).unwrap_or_default();
+            Err(ParseError::new(
+                7,
+                issue.line,
+                1,
+                text,
+                issue.problem.to_string(),
+            ))
+        }
+        None => Ok(fs),
+    }
+}
+
+/// Like [`input_generator_part_1`], also rejecting filesystems too big for the default disk, as
+/// part 2 has no answer for them.
+pub fn input_generator_part_2(input: &str) -> Result<FileSystem, ParseError> {
+    let fs = input_generator_part_1(input)?;
+    let disk = Disk::default();
+    if disk.free(&fs).is_none() {
+        let (line, text) = input.lines().enumerate().last().unwrap_or_default();
+        return Err(ParseError::at_end(
+            7,
+            line + 1,
+            text,
+            format!(
+                "expected the files to fit on a disk of {}, but they add up to {}",
+                disk.total,
+                fs.size(FileSystem::ROOT)
+            ),
+        ));
+    }
+    Ok(fs)
+}
+
+/// Renders a [`FileSystem::du`] report, one `<size>\t<path>` line per directory.
+pub fn render_du(report: &[(String, u64)]) -> String {
+    report
+        .iter()
+        .map(|(path, size)| format!("{}\t{}\n", size, path))
+        .collect()
 }
 
-//fn input_generator_part_2(input: &str) -> Vec<Placeholder> {}
+/// The disk the filesystem lives on, and how much of it the update needs free.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Disk {
+    pub total: u64,
+    pub required: u64,
+}
+
+impl Disk {
+    /// The space left once the filesystem is on the disk, or `None` if it doesn't fit.
+    pub fn free(&self, fs: &FileSystem) -> Option<u64> {
+        self.total.checked_sub(fs.sizes()[FileSystem::ROOT])
+    }
+}
+
+impl Default for Disk {
+    fn default() -> Disk {
+        Disk {
+            total: 70_000_000,
+            required: 30_000_000,
+        }
+    }
+}
+
+pub fn solve_part1(input: &FileSystem) -> u64 {
+    let small_dirs = Query {
+        kind: Some(Kind::Directory),
+        max_size: Some(100_000),
+        ..Query::default()
+    };
+    let matches = small_dirs.run(input).expect("The root always exists");
+    matches.iter().map(|m| m.size).sum()
+}
 
-fn solve_part1(input: &Directory) -> u32 {
-    todo!()
+/// The smallest directory that frees up enough space when deleted, or `None` if the filesystem
+/// doesn't even fit on the disk.
+pub fn directory_to_delete(input: &FileSystem, disk: Disk) -> Option<Match> {
+    let free = disk.free(input)?;
+    let smallest_big_enough = Query {
+        kind: Some(Kind::Directory),
+        min_size: Some(disk.required.saturating_sub(free)),
+        sort: SortKey::Size,
+        limit: Some(1),
+        ..Query::default()
+    };
+    let matches = smallest_big_enough
+        .run(input)
+        .expect("The root always exists");
+    matches.into_iter().next()
 }
-//fn solve_part2(input: &[Placeholder]) -> u32 {
-//todo!()
-//}
 
-pub fn main() {
-    let contents =
-        fs::read_to_string("input/2022/dayn.txt").expect("Should have been able to read the file");
-    let input_part_1 = input_generator_part_1(contents.as_str());
-    //let input_part_2 = input_generator_part_2(contents.as_str());
-    let part1_answer = solve_part1(&input_part_1);
-    println!("Part 1:\n\n{}\n\n\n", part1_answer);
-    //let part2_answer = solve_part2(input_part_2);
-    //println!("Part 2:\n\n{}\n\n\n", part2_answer);
+/// The size of [`directory_to_delete`].
+pub fn solve_part2_with(input: &FileSystem, disk: Disk) -> Option<u64> {
+    directory_to_delete(input, disk).map(|m| m.size)
+}
+
+pub fn solve_part2(input: &FileSystem) -> u64 {
+    solve_part2_with(input, Disk::default())
+        .expect("The input generator only lets through filesystems that fit on the disk")
+}
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    type Input = FileSystem;
+    type Answer = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input_generator_part_1(input)
+    }
+    fn solve(input: &Self::Input) -> Self::Answer {
+        solve_part1(input)
+    }
+}
+
+pub struct Part2;
+
+impl Solution for Part2 {
+    type Input = FileSystem;
+    type Answer = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input_generator_part_2(input)
+    }
+    fn solve(input: &Self::Input) -> Self::Answer {
+        solve_part2(input)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const EXAMPLE: &str = include_str!("../examples/2022/day7/example1.txt");
+    const EXAMPLE_TREE: &str = include_str!("../examples/2022/day7/example1.tree");
+
     #[test]
     fn test_input_generator() {
-        let input = input_generator_part_1("$ cd /\n$ ls\ndir a\n14848514 b.txt\n8504156 c.dat\ndir d\n$ cd a\n$ ls\ndir e\n29116 f\n2557 g\n62596 h.lst\n$ cd e\n$ ls\n584 i\n$ cd ..\n$ cd ..\n$ cd d\n$ ls\n4060174 j\n8033020 d.log\n5626152 d.ext\n7214296 k");
+        let input = input_generator_part_1(EXAMPLE).unwrap();
+        assert_eq!(input, tree::parse(EXAMPLE_TREE).unwrap());
+        assert_ne!(input, FileSystem::new());
+    }
+    #[test]
+    fn test_input_generator_rejects_problems() {
+        let error = input_generator_part_1("$ cd /\n$ cd ..\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.message, "`cd ..` from the root");
+    }
+    #[test]
+    fn test_lookup() {
+        let fs = input_generator_part_1(EXAMPLE).unwrap();
+        assert_eq!(fs.lookup("/"), Some(FileSystem::ROOT));
+        let e = fs.lookup("/a/e").unwrap();
+        assert_eq!(fs.node(e).name, "e");
+        assert_eq!(fs.path(e), "/a/e");
+        assert_eq!(fs.size(e), 584);
+        assert_eq!(fs.path(fs.lookup("/a/h.lst").unwrap()), "/a/h.lst");
+        assert_eq!(fs.lookup("/a/x"), None);
+        assert_eq!(fs.lookup("a/e"), None);
+    }
+    #[test]
+    fn test_cd() {
+        let fs = input_generator_part_1(EXAMPLE).unwrap();
+        let a = fs.lookup("/a").unwrap();
+        let e = fs.cd(a, "e").unwrap();
+        assert_eq!(fs.cd(e, ".."), Some(a));
+        assert_eq!(fs.cd(e, "/"), Some(FileSystem::ROOT));
+        assert_eq!(fs.cd(FileSystem::ROOT, ".."), Some(FileSystem::ROOT));
+        assert_eq!(fs.cd(a, "f"), None);
+        assert_eq!(fs.cd(a, "x"), None);
+    }
+    #[test]
+    fn test_part_1_solver() {
+        let input = input_generator_part_1(EXAMPLE).unwrap();
+        let output = solve_part1(&input);
+        assert_eq!(output, 95437);
+    }
+    #[test]
+    fn test_input_generator_part_2() {
+        let input = input_generator_part_2(EXAMPLE).unwrap();
+        assert_eq!(input, tree::parse(EXAMPLE_TREE).unwrap());
+    }
+    #[test]
+    fn test_part_2_solver() {
+        let input = input_generator_part_2(EXAMPLE).unwrap();
+        let output = solve_part2(&input);
+        assert_eq!(output, 24933642);
+    }
+    #[test]
+    fn test_part_2_disk() {
+        let input = input_generator_part_2(EXAMPLE).unwrap();
+        let roomy = Disk {
+            total: 100_000_000,
+            required: 30_000_000,
+        };
+        assert_eq!(solve_part2_with(&input, roomy), Some(584));
+        let tiny = Disk {
+            total: 1_000,
+            required: 0,
+        };
+        assert_eq!(solve_part2_with(&input, tiny), None);
+    }
+    #[test]
+    fn test_part_2_too_big() {
+        let input = "$ cd /\n$ ls\n99999999 big\n";
+        assert_eq!(solve_part1(&input_generator_part_1(input).unwrap()), 0);
+        let error = input_generator_part_2(input).unwrap_err();
+        assert_eq!((error.line, error.column), (3, 13));
+        let day = crate::DAYS.iter().find(|day| day.day == 7).unwrap();
+        assert!(day.parts[1].run(input).is_err());
+    }
+    #[test]
+    fn test_part_2_deletion() {
+        let disk = Disk::default();
+        let before = input_generator_part_2(EXAMPLE).unwrap();
+        let victim = directory_to_delete(&before, disk).unwrap();
+        assert_eq!(victim.path, "/d");
+
+        let (parent, name) = victim.path.rsplit_once('/').unwrap();
+        let mut transcript = format!("{}$ cd /\n", EXAMPLE);
+        for dir in parent.split('/').filter(|dir| !dir.is_empty()) {
+            transcript.push_str(&format!("$ cd {}\n", dir));
+        }
+        transcript.push_str(&format!("$ rm -r {}\n", name));
+        let after = input_generator_part_2(&transcript).unwrap();
+
+        assert_eq!(after.lookup("/d"), None);
+        let (free_before, free_after) = (disk.free(&before).unwrap(), disk.free(&after).unwrap());
+        assert!(free_before < disk.required);
+        assert!(free_after >= disk.required);
+        assert_eq!(free_after - free_before, solve_part2(&before));
+    }
+    #[test]
+    fn test_du() {
+        let input = input_generator_part_1(EXAMPLE).unwrap();
+        let report = input.du();
+        assert_eq!(
+            render_du(&report),
+            "584\t/a/e\n94853\t/a\n24933642\t/d\n48381165\t/\n"
+        );
+        let sizes = input.sizes();
+        for (path, size) in report {
+            let id = input.lookup(&path).unwrap();
+            assert_eq!(sizes[id], input.size(id));
+            assert_eq!(size, input.size(id));
+        }
+    }
+
+    #[test]
+    fn test_parse_line() {
+        assert_eq!(
+            parse_line("$ cd /").unwrap().1,
+            Line::Command(Command::Cd("/"))
+        );
+        assert_eq!(
+            parse_line("$ cd ..").unwrap().1,
+            Line::Command(Command::Cd(".."))
+        );
         assert_eq!(
-            input,
-            Directory(vec![
-                DirectoryItem::Directory(Directory(vec![
-                    DirectoryItem::Directory(Directory(vec![DirectoryItem::File(File {
-                        size: 584
-                    })])),
-                    DirectoryItem::File(File { size: 29116 }),
-                    DirectoryItem::File(File { size: 2557 }),
-                    DirectoryItem::File(File { size: 62596 })
-                ])),
-                DirectoryItem::File(File { size: 14848514 }),
-                DirectoryItem::File(File { size: 8504156 }),
-                DirectoryItem::Directory(Directory(vec![DirectoryItem::File(File {
-                    size: 4060174
-                })]))
-            ])
+            parse_line("$ cd a").unwrap().1,
+            Line::Command(Command::Cd("a"))
         );
+        assert_eq!(
+            parse_line("$ cd ..a").unwrap().1,
+            Line::Command(Command::Cd("..a"))
+        );
+        assert_eq!(parse_line("$ ls").unwrap().1, Line::Command(Command::Ls));
+        assert_eq!(
+            parse_line("$ rm b.txt").unwrap().1,
+            Line::Command(Command::Rm("b.txt"))
+        );
+        assert_eq!(
+            parse_line("$ rm -r d").unwrap().1,
+            Line::Command(Command::RmRecursive("d"))
+        );
+        assert_eq!(
+            parse_line("$ mkdir x").unwrap().1,
+            Line::Command(Command::Mkdir("x"))
+        );
+        assert_eq!(
+            parse_line("$ touch 10 y.txt").unwrap().1,
+            Line::Command(Command::Touch(10, "y.txt"))
+        );
+        assert_eq!(parse_line("dir e").unwrap().1, Line::Directory("e"));
+        assert_eq!(
+            parse_line("62596 h.lst").unwrap().1,
+            Line::File(62596, "h.lst")
+        );
+    }
+    #[test]
+    fn test_parse_transcript() {
+        let lines = parse_transcript(EXAMPLE).unwrap();
+        assert_eq!(lines.len(), 23);
+        assert_eq!(lines[3], Line::File(14848514, "b.txt"));
+    }
+    #[test]
+    fn test_parse_transcript_errors() {
+        let error = parse_transcript("$ cd /\n$ ls\ndir\n").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 1));
+        let error = parse_transcript("$ cd /\n$ mv a").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(
+            error.message,
+            "expected `ls`, `cd`, `rm`, `mkdir` or `touch`"
+        );
+        let error = parse_transcript("$ touch a").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 9));
+        assert_eq!(error.message, "expected the size of the file");
+        let error = parse_transcript("$ cd\n").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 3));
+        let error = parse_transcript("$ ls\n12 \n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 4));
+        assert_eq!(error.message, "expected the name of the file");
+        for (line, column) in [
+            ("dir ..", 5),
+            ("5 a/b", 3),
+            ("$ mkdir .", 9),
+            ("$ touch 5 /", 11),
+            ("$ cd a/b", 6),
+        ] {
+            let error = parse_transcript(line).unwrap_err();
+            assert_eq!((error.line, error.column), (1, column), "{}", line);
+            assert_eq!(
+                error.message,
+                "expected a name without `/` that isn't `.` or `..`"
+            );
+        }
+        let error = parse_transcript("$ ls extra").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 5));
+        assert_eq!(error.message, "expected the end of the line");
     }
-    //#[test]
-    //fn test_part_1_solver() {
-    //let input = input_generator_part_1("DATA");
-    //let output = solve_part1(&input);
-    //assert_eq!(output, "ANSWER");
-    //}
-    //#[test]
-    //fn test_input_generator_part_2() {
-    //let input = input_generator_part_2("DATA");
-    //assert_eq!(input, []);
-    //}
-    //#[test]
-    //fn test_part_2_solver() {
-    //let input = input_generator_part_2("DATA");
-    //let output = solve_part2(&input);
-    //assert_eq!(output, "ANSWER");
-    //}
 }
@@ -0,0 +1,411 @@
+//! Finds the places where a transcript contradicts itself or couldn't have happened.
+
+use std::{collections::HashMap, fmt};
+
+use super::{Command, Directory, DirectoryItem, File, FileSystem, Line, NodeId};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Problem {
+    /// `cd` into a directory that isn't in the listing of the current one.
+    CdIntoUnlisted {
+        path: String,
+    },
+    CdAboveRoot,
+    /// A directory listed with different contents from an earlier listing, or from what was
+    /// already known to be in it.
+    ListedDifferently {
+        path: String,
+    },
+    FileUsedAsDirectory {
+        path: String,
+    },
+    DirectoryUsedAsFile {
+        path: String,
+    },
+    /// The same name twice in one listing.
+    DuplicateName {
+        path: String,
+    },
+    /// `dir` or file lines that no `ls` printed.
+    OutputWithoutLs,
+    RemoveMissing {
+        path: String,
+    },
+    /// `rm` without `-r` on a directory.
+    RemoveDirectory {
+        path: String,
+    },
+    /// `mkdir` or `touch` of a name that's already taken.
+    CreateExisting {
+        path: String,
+    },
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Problem::CdIntoUnlisted { path } => {
+                write!(f, "`cd` into {}, which was never listed", path)
+            }
+            Problem::CdAboveRoot => write!(f, "`cd ..` from the root"),
+            Problem::ListedDifferently { path } => {
+                write!(
+                    f,
+                    "{} was listed with contents that contradict what came before",
+                    path
+                )
+            }
+            Problem::FileUsedAsDirectory { path } => {
+                write!(f, "{} is a file but is used as a directory", path)
+            }
+            Problem::DirectoryUsedAsFile { path } => {
+                write!(f, "{} is a directory but is listed as a file", path)
+            }
+            Problem::DuplicateName { path } => write!(f, "{} is listed twice", path),
+            Problem::OutputWithoutLs => write!(f, "output without an `ls` before it"),
+            Problem::RemoveMissing { path } => write!(f, "`rm` of {}, which doesn't exist", path),
+            Problem::RemoveDirectory { path } => {
+                write!(f, "{} is a directory, so needs `rm -r`", path)
+            }
+            Problem::CreateExisting { path } => write!(f, "{} already exists", path),
+        }
+    }
+}
+
+/// A problem, and the 1-based transcript line it was found on.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Issue {
+    pub line: usize,
+    pub problem: Problem,
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.problem)
+    }
+}
+
+/// What one `ls` printed: each name, with its size if it's a file.
+type Listing<'a> = Vec<(&'a str, Option<u64>)>;
+
+fn join(fs: &FileSystem, dir: NodeId, name: &str) -> String {
+    match dir {
+        FileSystem::ROOT => format!("/{}", name),
+        dir => format!("{}/{}", fs.path(dir), name),
+    }
+}
+
+/// Records the listing that just ended, or the problem if it contradicts an earlier one.
+fn finish<'a>(
+    listed: &mut HashMap<NodeId, Listing<'a>>,
+    listing: Option<(NodeId, usize, Listing<'a>)>,
+    fs: &FileSystem,
+) -> Option<Issue> {
+    let (dir, line, mut entries) = listing?;
+    entries.sort();
+    match listed.get(&dir) {
+        Some(previous) if *previous != entries => Some(Issue {
+            line,
+            problem: Problem::ListedDifferently { path: fs.path(dir) },
+        }),
+        Some(_) => None,
+        None => {
+            // Nothing was listed here before, but `mkdir`, `touch` or `cd` may have made things
+            let mut known: Listing = fs
+                .children(dir)
+                .iter()
+                .map(|&child| {
+                    let size = match &fs.node(child).item {
+                        DirectoryItem::File(file) => Some(file.size),
+                        DirectoryItem::Directory(_) => None,
+                    };
+                    (fs.node(child).name.as_str(), size)
+                })
+                .collect();
+            known.sort();
+            if known != entries {
+                return Some(Issue {
+                    line,
+                    problem: Problem::ListedDifferently { path: fs.path(dir) },
+                });
+            }
+            listed.insert(dir, entries);
+            None
+        }
+    }
+}
+
+/// Adds one entry printed by `ls` to the listing and the filesystem, if it makes sense.
+fn list<'a>(
+    fs: &mut FileSystem,
+    listing: &mut Option<(NodeId, usize, Listing<'a>)>,
+    name: &'a str,
+    size: Option<u64>,
+) -> Option<Problem> {
+    let Some((dir, _, entries)) = listing else {
+        return Some(Problem::OutputWithoutLs);
+    };
+    if entries.iter().any(|(other, _)| *other == name) {
+        return Some(Problem::DuplicateName {
+            path: join(fs, *dir, name),
+        });
+    }
+    entries.push((name, size));
+    match (fs.child(*dir, name), size) {
+        (Some(existing), None) if !fs.is_dir(existing) => Some(Problem::FileUsedAsDirectory {
+            path: fs.path(existing),
+        }),
+        (Some(existing), Some(_)) if fs.is_dir(existing) => Some(Problem::DirectoryUsedAsFile {
+            path: fs.path(existing),
+        }),
+        (Some(_), _) => None,
+        (None, None) => {
+            fs.add(*dir, name, DirectoryItem::Directory(Directory::default()));
+            None
+        }
+        (None, Some(size)) => {
+            fs.add(*dir, name, DirectoryItem::File(File { size }));
+            None
+        }
+    }
+}
+
+/// Carries out `rm`, `mkdir` or `touch` in `cwd`, keeping what was listed there in step so that
+/// listing it again isn't a contradiction.
+fn change<'a>(
+    fs: &mut FileSystem,
+    listed: &mut HashMap<NodeId, Listing<'a>>,
+    cwd: NodeId,
+    command: Command<'a>,
+) -> Option<Problem> {
+    let (name, created) = match command {
+        Command::Ls | Command::Cd(_) => unreachable!("Only changes are passed in"),
+        Command::Rm(name) | Command::RmRecursive(name) => (name, None),
+        Command::Mkdir(name) => (name, Some(None)),
+        Command::Touch(size, name) => (name, Some(Some(size))),
+    };
+    let existing = fs.child(cwd, name);
+    match (existing, created) {
+        (None, None) => Some(Problem::RemoveMissing {
+            path: join(fs, cwd, name),
+        }),
+        (Some(id), None) if fs.is_dir(id) && matches!(command, Command::Rm(_)) => {
+            Some(Problem::RemoveDirectory { path: fs.path(id) })
+        }
+        (Some(id), None) => {
+            fs.remove(id);
+            if let Some(entries) = listed.get_mut(&cwd) {
+                entries.retain(|(listed, _)| *listed != name);
+            }
+            None
+        }
+        (Some(id), Some(_)) => Some(Problem::CreateExisting { path: fs.path(id) }),
+        (None, Some(size)) => {
+            let item = match size {
+                Some(size) => DirectoryItem::File(File { size }),
+                None => DirectoryItem::Directory(Directory::default()),
+            };
+            fs.add(cwd, name, item);
+            if let Some(entries) = listed.get_mut(&cwd) {
+                entries.push((name, size));
+                entries.sort();
+            }
+            None
+        }
+    }
+}
+
+/// Builds the filesystem as best it can, along with every problem found on the way, in line
+/// order. Directories changed into without being listed are created, and everything else that
+/// doesn't make sense is skipped.
+pub fn check(lines: &[Line]) -> (FileSystem, Vec<Issue>) {
+    let mut fs = FileSystem::new();
+    let mut issues = vec![];
+    let mut cwd = FileSystem::ROOT;
+    let mut listed = HashMap::new();
+    // The directory being listed, the line of its `ls`, and what has been listed so far
+    let mut listing: Option<(NodeId, usize, Listing)> = None;
+
+    for (i, line) in lines.iter().enumerate() {
+        let problem = match *line {
+            Line::Command(command) => {
+                issues.extend(finish(&mut listed, listing.take(), &fs));
+                match command {
+                    Command::Ls => {
+                        listing = Some((cwd, i + 1, vec![]));
+                        None
+                    }
+                    Command::Cd("/") => {
+                        cwd = FileSystem::ROOT;
+                        None
+                    }
+                    Command::Cd("..") => match fs.node(cwd).parent {
+                        Some(parent) => {
+                            cwd = parent;
+                            None
+                        }
+                        None => Some(Problem::CdAboveRoot),
+                    },
+                    Command::Cd(name) => match fs.child(cwd, name) {
+                        Some(child) if fs.is_dir(child) => {
+                            cwd = child;
+                            None
+                        }
+                        Some(child) => Some(Problem::FileUsedAsDirectory {
+                            path: fs.path(child),
+                        }),
+                        None => {
+                            let path = join(&fs, cwd, name);
+                            cwd = fs.add(cwd, name, DirectoryItem::Directory(Directory::default()));
+                            Some(Problem::CdIntoUnlisted { path })
+                        }
+                    },
+                    change_command => change(&mut fs, &mut listed, cwd, change_command),
+                }
+            }
+            Line::Directory(name) => list(&mut fs, &mut listing, name, None),
+            Line::File(size, name) => list(&mut fs, &mut listing, name, Some(size)),
+        };
+        issues.extend(problem.map(|problem| Issue {
+            line: i + 1,
+            problem,
+        }));
+    }
+    issues.extend(finish(&mut listed, listing.take(), &fs));
+    issues.sort_by_key(|issue| issue.line);
+    (fs, issues)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day7::parse_transcript;
+
+    const EXAMPLE: &str = include_str!("../../examples/2022/day7/example1.txt");
+
+    fn problems(transcript: &str) -> Vec<(usize, Problem)> {
+        check(&parse_transcript(transcript).unwrap())
+            .1
+            .into_iter()
+            .map(|issue| (issue.line, issue.problem))
+            .collect()
+    }
+
+    #[test]
+    fn test_check_example() {
+        assert_eq!(problems(EXAMPLE), vec![]);
+    }
+    #[test]
+    fn test_check_cd() {
+        assert_eq!(
+            problems("$ cd /\n$ ls\ndir a\n2 b\n$ cd c\n$ cd ..\n$ cd ..\n$ cd b\n"),
+            vec![
+                (
+                    5,
+                    Problem::CdIntoUnlisted {
+                        path: "/c".to_owned()
+                    }
+                ),
+                (7, Problem::CdAboveRoot),
+                (
+                    8,
+                    Problem::FileUsedAsDirectory {
+                        path: "/b".to_owned()
+                    }
+                ),
+            ]
+        );
+    }
+    #[test]
+    fn test_check_listings() {
+        assert_eq!(
+            problems("$ cd /\n1 x\n$ ls\ndir a\n2 b\n2 b\n$ ls\ndir a\ndir b\n$ ls\n3 a\n2 b\n"),
+            vec![
+                (2, Problem::OutputWithoutLs),
+                (
+                    6,
+                    Problem::DuplicateName {
+                        path: "/b".to_owned()
+                    }
+                ),
+                (
+                    7,
+                    Problem::ListedDifferently {
+                        path: "/".to_owned()
+                    }
+                ),
+                (
+                    9,
+                    Problem::FileUsedAsDirectory {
+                        path: "/b".to_owned()
+                    }
+                ),
+                (
+                    10,
+                    Problem::ListedDifferently {
+                        path: "/".to_owned()
+                    }
+                ),
+                (
+                    11,
+                    Problem::DirectoryUsedAsFile {
+                        path: "/a".to_owned()
+                    }
+                ),
+            ]
+        );
+    }
+    #[test]
+    fn test_check_changes() {
+        assert_eq!(
+            problems(
+                "$ ls\ndir a\n1 b\n$ rm c\n$ rm a\n$ mkdir b\n$ touch 2 a\n$ rm -r a\n$ rm b\n\
+                 $ mkdir b\n$ touch 3 c\n$ ls\ndir b\n3 c\n"
+            ),
+            vec![
+                (
+                    4,
+                    Problem::RemoveMissing {
+                        path: "/c".to_owned()
+                    }
+                ),
+                (
+                    5,
+                    Problem::RemoveDirectory {
+                        path: "/a".to_owned()
+                    }
+                ),
+                (
+                    6,
+                    Problem::CreateExisting {
+                        path: "/b".to_owned()
+                    }
+                ),
+                (
+                    7,
+                    Problem::CreateExisting {
+                        path: "/a".to_owned()
+                    }
+                ),
+            ]
+        );
+        assert_eq!(problems("$ ls\n1 b\n$ rm b\n$ ls\n1 b\n").len(), 1);
+        assert_eq!(
+            problems("$ cd /\n$ mkdir a\n$ touch 5 c\n$ ls\n1 b\n"),
+            vec![(
+                4,
+                Problem::ListedDifferently {
+                    path: "/".to_owned()
+                }
+            )]
+        );
+        assert_eq!(
+            problems("$ cd /\n$ mkdir a\n$ touch 5 c\n$ ls\ndir a\n5 c\n"),
+            vec![]
+        );
+    }
+    #[test]
+    fn test_relisting_is_fine() {
+        assert_eq!(problems("$ ls\ndir a\n1 b\n$ ls\n1 b\ndir a\n"), vec![]);
+    }
+}
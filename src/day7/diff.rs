@@ -0,0 +1,227 @@
+//! What changed between two filesystems, such as before and after a cleanup session.
+
+use super::{query::Kind, DirectoryItem, FileSystem, NodeId};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Change {
+    Added,
+    Removed,
+    /// A file whose size changed, or a directory with changes somewhere inside it.
+    Changed {
+        before: u64,
+        after: u64,
+    },
+    Unchanged,
+}
+
+/// One node of the diff, which is a tree covering everything in either filesystem. Something
+/// that changed from a file to a directory, or the other way round, is removed and added again.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DiffNode {
+    pub name: String,
+    pub kind: Kind,
+    /// The total size before, or `None` if it was added.
+    pub before: Option<u64>,
+    /// The total size after, or `None` if it was removed.
+    pub after: Option<u64>,
+    pub children: Vec<DiffNode>,
+}
+
+impl DiffNode {
+    pub fn change(&self) -> Change {
+        match (self.before, self.after) {
+            (None, _) => Change::Added,
+            (_, None) => Change::Removed,
+            (Some(before), Some(after)) if before != after => Change::Changed { before, after },
+            _ if self
+                .children
+                .iter()
+                .all(|child| child.change() == Change::Unchanged) =>
+            {
+                Change::Unchanged
+            }
+            // Things moved around inside it without changing its total
+            (Some(before), Some(after)) => Change::Changed { before, after },
+        }
+    }
+
+    /// How much bigger it got, negative if it shrank.
+    pub fn delta(&self) -> i128 {
+        self.after.unwrap_or(0) as i128 - self.before.unwrap_or(0) as i128
+    }
+}
+
+fn kind(fs: &FileSystem, id: NodeId) -> Kind {
+    match fs.node(id).item {
+        DirectoryItem::Directory(_) => Kind::Directory,
+        DirectoryItem::File(_) => Kind::File,
+    }
+}
+
+/// Everything in `id` and below, all added or all removed.
+fn one_sided(fs: &FileSystem, sizes: &[u64], id: NodeId, added: bool) -> DiffNode {
+    let size = Some(sizes[id]);
+    DiffNode {
+        name: fs.node(id).name.clone(),
+        kind: kind(fs, id),
+        before: if added { None } else { size },
+        after: if added { size } else { None },
+        children: fs
+            .children(id)
+            .iter()
+            .map(|&child| one_sided(fs, sizes, child, added))
+            .collect(),
+    }
+}
+
+/// Compares the trees, matching up entries by name.
+pub fn diff(before: &FileSystem, after: &FileSystem) -> DiffNode {
+    struct Sides<'a> {
+        before: &'a FileSystem,
+        after: &'a FileSystem,
+        before_sizes: Vec<u64>,
+        after_sizes: Vec<u64>,
+    }
+
+    fn compare(sides: &Sides, old: NodeId, new: NodeId) -> DiffNode {
+        let mut children = vec![];
+        for &child in sides.before.children(old) {
+            let name = &sides.before.node(child).name;
+            match sides.after.child(new, name) {
+                Some(other) if kind(sides.before, child) == kind(sides.after, other) => {
+                    children.push(compare(sides, child, other))
+                }
+                Some(other) => {
+                    children.push(one_sided(sides.before, &sides.before_sizes, child, false));
+                    children.push(one_sided(sides.after, &sides.after_sizes, other, true));
+                }
+                None => children.push(one_sided(sides.before, &sides.before_sizes, child, false)),
+            }
+        }
+        for &child in sides.after.children(new) {
+            if sides
+                .before
+                .child(old, &sides.after.node(child).name)
+                .is_none()
+            {
+                children.push(one_sided(sides.after, &sides.after_sizes, child, true));
+            }
+        }
+        DiffNode {
+            name: sides.before.node(old).name.clone(),
+            kind: kind(sides.before, old),
+            before: Some(sides.before_sizes[old]),
+            after: Some(sides.after_sizes[new]),
+            children,
+        }
+    }
+
+    let sides = Sides {
+        before,
+        after,
+        before_sizes: before.sizes(),
+        after_sizes: after.sizes(),
+    };
+    compare(&sides, FileSystem::ROOT, FileSystem::ROOT)
+}
+
+/// Renders what changed in the puzzle's tree format, marking each entry `+` if added, `-` if
+/// removed and `~` if it changed. Unchanged entries are left out, as is everything inside
+/// an added or removed directory; the root is always shown, marked `=` if nothing changed.
+pub fn render(diff: &DiffNode) -> String {
+    fn line(node: &DiffNode, depth: usize, out: &mut String) {
+        let kind = match node.kind {
+            Kind::Directory => "dir",
+            Kind::File => "file",
+        };
+        let (marker, size) = match node.change() {
+            Change::Added => ('+', format!("size={}", node.delta())),
+            Change::Removed => ('-', format!("size={}", -node.delta())),
+            Change::Changed { before, after } => (
+                '~',
+                format!("size={} -> {}, {:+}", before, after, node.delta()),
+            ),
+            Change::Unchanged => ('=', format!("size={}", node.after.unwrap_or_default())),
+        };
+        out.push_str(&format!(
+            "{}{} {} ({}, {})\n",
+            "  ".repeat(depth),
+            marker,
+            node.name,
+            kind,
+            size
+        ));
+        if let Change::Changed { .. } = node.change() {
+            for child in &node.children {
+                if child.change() != Change::Unchanged {
+                    line(child, depth + 1, out);
+                }
+            }
+        }
+    }
+
+    let mut out = String::new();
+    line(diff, 0, &mut out);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day7::input_generator_part_1;
+
+    const EXAMPLE: &str = include_str!("../../examples/2022/day7/example1.txt");
+
+    #[test]
+    fn test_diff_same() {
+        let fs = input_generator_part_1(EXAMPLE).unwrap();
+        let diff = diff(&fs, &fs);
+        assert_eq!(diff.change(), Change::Unchanged);
+        assert_eq!(render(&diff), "= / (dir, size=48381165)\n");
+    }
+    #[test]
+    fn test_diff() {
+        let before = input_generator_part_1(EXAMPLE).unwrap();
+        let after = input_generator_part_1(
+            "$ cd /\n$ ls\ndir a\n14848514 b.txt\n8504156 c.dat\ndir d\n100 x\n\
+             $ cd a\n$ ls\ndir e\n29116 f\n2557 g\n62596 h.lst\n$ cd e\n$ ls\n600 i\n\
+             $ cd /\n$ cd d\n$ ls\n4060174 j\n5626152 d.ext\ndir k\n",
+        )
+        .unwrap();
+        let diff = diff(&before, &after);
+        assert_eq!(diff.delta(), 100 + 16 - 8033020 - 7214296);
+        assert_eq!(
+            render(&diff),
+            "~ / (dir, size=48381165 -> 33133965, -15247200)\n\
+             \x20 ~ a (dir, size=94853 -> 94869, +16)\n\
+             \x20   ~ e (dir, size=584 -> 600, +16)\n\
+             \x20     ~ i (file, size=584 -> 600, +16)\n\
+             \x20 ~ d (dir, size=24933642 -> 9686326, -15247316)\n\
+             \x20   - d.log (file, size=8033020)\n\
+             \x20   - k (file, size=7214296)\n\
+             \x20   + k (dir, size=0)\n\
+             \x20 + x (file, size=100)\n"
+        );
+    }
+    #[test]
+    fn test_diff_moved_file() {
+        let before = input_generator_part_1("$ ls\ndir a\ndir b\n$ cd a\n$ ls\n5 f\n").unwrap();
+        let after = input_generator_part_1("$ ls\ndir a\ndir b\n$ cd b\n$ ls\n5 f\n").unwrap();
+        let diff = diff(&before, &after);
+        assert_eq!(
+            diff.change(),
+            Change::Changed {
+                before: 5,
+                after: 5
+            }
+        );
+        assert_eq!(
+            render(&diff),
+            "~ / (dir, size=5 -> 5, +0)\n\
+             \x20 ~ a (dir, size=5 -> 0, -5)\n\
+             \x20   - f (file, size=5)\n\
+             \x20 ~ b (dir, size=0 -> 5, +5)\n\
+             \x20   + f (file, size=5)\n"
+        );
+    }
+}
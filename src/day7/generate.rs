@@ -0,0 +1,323 @@
+//! Builds filesystems, from a directory on disk or at random, and writes them out as transcripts
+//! to feed back into the parser.
+
+use std::{fs, io, path::Path};
+
+use super::{Directory, DirectoryItem, Disk, File, FileSystem, NodeId};
+
+/// How file sizes are picked for a random tree.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SizeDistribution {
+    Uniform {
+        min: u64,
+        max: u64,
+    },
+    /// Spread evenly across orders of magnitude, so most files are small, like on a real disk.
+    LogUniform {
+        min: u64,
+        max: u64,
+    },
+}
+
+/// The shape of a random tree.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RandomTree {
+    /// How many directories deep the tree can go below the root.
+    pub max_depth: usize,
+    /// The most entries any one directory can have.
+    pub max_fan_out: usize,
+    /// The chance of each entry being a directory rather than a file, while the tree is
+    /// allowed to go deeper.
+    pub dir_chance: f64,
+    pub sizes: SizeDistribution,
+}
+
+impl Default for RandomTree {
+    fn default() -> RandomTree {
+        RandomTree {
+            max_depth: 4,
+            max_fan_out: 6,
+            dir_chance: 0.3,
+            sizes: SizeDistribution::LogUniform {
+                min: 1,
+                max: 300_000,
+            },
+        }
+    }
+}
+
+impl RandomTree {
+    /// The most bytes a tree of this shape could hold, or `None` if that could be more than a
+    /// `u64` can count, in which case the directory sizes could overflow.
+    pub fn max_total(&self) -> Option<u64> {
+        let max_size = match self.sizes {
+            SizeDistribution::Uniform { max, .. } | SizeDistribution::LogUniform { max, .. } => max,
+        };
+        // Every entry at the deepest level could be a file
+        let max_files = u32::try_from(self.max_depth + 1)
+            .ok()
+            .and_then(|levels| (self.max_fan_out as u64).checked_pow(levels))?;
+        max_files.checked_mul(max_size)
+    }
+}
+
+/// SplitMix64, so that a seed gives the same tree everywhere.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0.0..1.0`.
+    fn fraction(&mut self) -> f64 {
+        (self.next() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// A number in `min..=max`.
+    fn between(&mut self, min: u64, max: u64) -> u64 {
+        match (max - min).checked_add(1) {
+            Some(span) => min + self.next() % span,
+            // The whole range of a u64
+            None => self.next(),
+        }
+    }
+
+    fn size(&mut self, sizes: SizeDistribution) -> u64 {
+        match sizes {
+            SizeDistribution::Uniform { min, max } => self.between(min, max),
+            SizeDistribution::LogUniform { min, max } => {
+                let (low, high) = ((min.max(1) as f64).ln(), (max as f64).ln());
+                ((low + self.fraction() * (high - low)).exp() as u64).clamp(min, max)
+            }
+        }
+    }
+
+    fn name(&mut self, extension: bool) -> String {
+        const EXTENSIONS: &[&str] = &["txt", "dat", "lst", "log", "ext"];
+        let mut name: String = (0..self.between(1, 8))
+            .map(|_| (b'a' + self.between(0, 25) as u8) as char)
+            .collect();
+        if extension && self.fraction() < 0.5 {
+            name.push('.');
+            name.push_str(EXTENSIONS[self.between(0, EXTENSIONS.len() as u64 - 1) as usize]);
+        }
+        name
+    }
+}
+
+/// A random tree with the given shape, the same every time for the same seed.
+pub fn random(shape: RandomTree, seed: u64) -> FileSystem {
+    fn grow(fs: &mut FileSystem, rng: &mut Rng, shape: RandomTree, dir: NodeId, depth: usize) {
+        for _ in 0..rng.between(0, shape.max_fan_out as u64) {
+            let is_dir = depth < shape.max_depth && rng.fraction() < shape.dir_chance;
+            let name = rng.name(!is_dir);
+            if fs.child(dir, &name).is_some() {
+                continue;
+            }
+            if is_dir {
+                let child = fs.add(dir, &name, DirectoryItem::Directory(Directory::default()));
+                grow(fs, rng, shape, child, depth + 1);
+            } else {
+                let size = rng.size(shape.sizes);
+                fs.add(dir, &name, DirectoryItem::File(File { size }));
+            }
+        }
+    }
+
+    let mut fs = FileSystem::new();
+    grow(&mut fs, &mut Rng(seed), shape, FileSystem::ROOT, 0);
+    fs
+}
+
+/// The tree under `path` on disk, with each file's size in bytes. Symlinks are not followed, and
+/// anything whose name can't appear in a transcript, because it has whitespace in it or isn't
+/// UTF-8, is left out.
+pub fn from_dir(path: &Path) -> io::Result<FileSystem> {
+    fn walk(fs: &mut FileSystem, dir: NodeId, path: &Path) -> io::Result<()> {
+        let mut entries = fs::read_dir(path)?.collect::<io::Result<Vec<_>>>()?;
+        entries.sort_by_key(|entry| entry.file_name());
+        for entry in entries {
+            let Ok(name) = entry.file_name().into_string() else {
+                continue;
+            };
+            if name.is_empty() || name.contains(char::is_whitespace) {
+                continue;
+            }
+            let kind = entry.file_type()?;
+            if kind.is_dir() {
+                let child = fs.add(dir, &name, DirectoryItem::Directory(Directory::default()));
+                walk(fs, child, &entry.path())?;
+            } else if kind.is_file() {
+                let size = entry.metadata()?.len();
+                fs.add(dir, &name, DirectoryItem::File(File { size }));
+            }
+        }
+        Ok(())
+    }
+
+    let mut fs = FileSystem::new();
+    walk(&mut fs, FileSystem::ROOT, path)?;
+    Ok(fs)
+}
+
+/// A transcript that explores the whole tree the way the puzzle's does: list a directory, then
+/// `cd` into each directory in it in turn.
+pub fn transcript(fs: &FileSystem) -> String {
+    fn explore(fs: &FileSystem, dir: NodeId, transcript: &mut String) {
+        transcript.push_str("$ ls\n");
+        for &child in fs.children(dir) {
+            let node = fs.node(child);
+            match &node.item {
+                DirectoryItem::Directory(_) => transcript.push_str(&format!("dir {}\n", node.name)),
+                DirectoryItem::File(file) => {
+                    transcript.push_str(&format!("{} {}\n", file.size, node.name))
+                }
+            }
+        }
+        for &child in fs.children(dir) {
+            if fs.is_dir(child) {
+                transcript.push_str(&format!("$ cd {}\n", fs.node(child).name));
+                explore(fs, child, transcript);
+                transcript.push_str("$ cd ..\n");
+            }
+        }
+    }
+
+    let mut transcript = String::from("$ cd /\n");
+    explore(fs, FileSystem::ROOT, &mut transcript);
+    // There's no need to climb back out of the last directory
+    while transcript.ends_with("$ cd ..\n") {
+        transcript.truncate(transcript.len() - "$ cd ..\n".len());
+    }
+    transcript
+}
+
+/// The part 1 and part 2 answers for a tree, worked out separately from the solvers by sizing
+/// each directory on its own. Part 2 has no answer when the tree doesn't fit on the disk.
+pub fn expected_answers(fs: &FileSystem, disk: Disk) -> (u64, Option<u64>) {
+    let dirs: Vec<u64> = fs
+        .walk()
+        .into_iter()
+        .filter(|&id| fs.is_dir(id))
+        .map(|id| fs.size(id))
+        .collect();
+    let part1 = dirs.iter().filter(|&&size| size <= 100_000).sum();
+    let used = dirs[0];
+    let part2 = (used <= disk.total).then(|| {
+        let needed = (disk.required + used).saturating_sub(disk.total);
+        *dirs
+            .iter()
+            .filter(|&&size| size >= needed)
+            .min()
+            .expect("The root is always big enough")
+    });
+    (part1, part2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day7::{
+        check::check, input_generator_part_1, parse_transcript, solve_part1, solve_part2_with, tree,
+    };
+
+    const EXAMPLE: &str = include_str!("../../examples/2022/day7/example1.txt");
+
+    #[test]
+    fn test_transcript() {
+        let fs = input_generator_part_1(EXAMPLE).unwrap();
+        assert_eq!(transcript(&fs), EXAMPLE);
+    }
+    #[test]
+    fn test_expected_answers() {
+        let fs = input_generator_part_1(EXAMPLE).unwrap();
+        assert_eq!(
+            expected_answers(&fs, Disk::default()),
+            (95437, Some(24933642))
+        );
+    }
+    #[test]
+    fn test_random() {
+        let shape = RandomTree {
+            max_depth: 6,
+            max_fan_out: 10,
+            ..RandomTree::default()
+        };
+        assert_eq!(random(shape, 7), random(shape, 7));
+        for seed in 0..20 {
+            let fs = random(shape, seed);
+            let transcript = transcript(&fs);
+            let (_, issues) = check(&parse_transcript(&transcript).unwrap());
+            assert_eq!(issues, vec![]);
+
+            let parsed = input_generator_part_1(&transcript).unwrap();
+            assert_eq!(parsed, fs, "{}", tree::render(&fs, false));
+            let (part1, part2) = expected_answers(&fs, Disk::default());
+            assert_eq!(solve_part1(&parsed), part1);
+            assert_eq!(solve_part2_with(&parsed, Disk::default()), part2);
+        }
+    }
+    #[test]
+    fn test_random_sizes() {
+        let shape = RandomTree {
+            sizes: SizeDistribution::Uniform { min: 10, max: 20 },
+            ..RandomTree::default()
+        };
+        let fs = random(shape, 1);
+        assert!(fs
+            .walk()
+            .into_iter()
+            .filter(|&id| !fs.is_dir(id))
+            .all(|id| (10..=20).contains(&fs.size(id))));
+    }
+    #[test]
+    fn test_random_full_range() {
+        let mut rng = Rng(3);
+        assert_eq!(rng.between(5, 5), 5);
+        rng.between(0, u64::MAX);
+        let shape = RandomTree {
+            sizes: SizeDistribution::Uniform {
+                min: 0,
+                max: u64::MAX,
+            },
+            ..RandomTree::default()
+        };
+        assert_eq!(random(shape, 2), random(shape, 2));
+    }
+    #[test]
+    fn test_max_total() {
+        assert_eq!(
+            RandomTree::default().max_total(),
+            Some(6u64.pow(5) * 300_000)
+        );
+        let huge = RandomTree {
+            sizes: SizeDistribution::Uniform {
+                min: 0,
+                max: u64::MAX,
+            },
+            ..RandomTree::default()
+        };
+        assert_eq!(huge.max_total(), None);
+    }
+    #[test]
+    fn test_from_dir() {
+        let root = std::env::temp_dir().join(format!("aoc-day7-{}", std::process::id()));
+        fs::create_dir_all(root.join("a/e")).unwrap();
+        fs::write(root.join("a/e/i"), "x".repeat(584)).unwrap();
+        fs::write(root.join("b.txt"), "hello").unwrap();
+        fs::write(root.join("has space"), "skipped").unwrap();
+
+        let found = from_dir(&root).unwrap();
+        assert_eq!(
+            tree::render(&found, false),
+            "- / (dir)\n  - a (dir)\n    - e (dir)\n      - i (file, size=584)\n  - b.txt (file, size=5)\n"
+        );
+        assert_eq!(input_generator_part_1(&transcript(&found)).unwrap(), found);
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,246 @@
+//! The filesystem as a JSON document, for other tools to draw or pick apart:
+//!
+//! ```json
+//! {
+//!   "type": "dir",
+//!   "name": "/",
+//!   "total_size": 584,
+//!   "children": [{ "type": "file", "name": "i", "size": 584 }]
+//! }
+//! ```
+
+use std::{error::Error, fmt};
+
+use serde::{Deserialize, Serialize};
+
+use super::{is_valid_name, Directory, DirectoryItem, File, FileSystem, NodeId};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum JsonNode {
+    File {
+        name: String,
+        size: u64,
+    },
+    Dir {
+        name: String,
+        /// Always written, and checked when given on the way in.
+        #[serde(default)]
+        total_size: Option<u64>,
+        #[serde(default)]
+        children: Vec<JsonNode>,
+    },
+}
+
+#[derive(Debug)]
+pub enum JsonError {
+    Json(serde_json::Error),
+    /// The top of the document isn't a directory called `/`.
+    Root,
+    /// A name that couldn't appear in a transcript, such as `..` or one with whitespace in it.
+    Name(String),
+    Duplicate {
+        path: String,
+    },
+    TotalSize {
+        path: String,
+        claimed: u64,
+        actual: u64,
+    },
+}
+
+impl fmt::Display for JsonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JsonError::Json(error) => write!(f, "{}", error),
+            JsonError::Root => write!(f, "expected the root to be a directory called `/`"),
+            JsonError::Name(name) => write!(f, "{:?} can't be used as a name", name),
+            JsonError::Duplicate { path } => write!(f, "{} is in its directory twice", path),
+            JsonError::TotalSize {
+                path,
+                claimed,
+                actual,
+            } => write!(
+                f,
+                "{} has a total_size of {}, but its contents add up to {}",
+                path, claimed, actual
+            ),
+        }
+    }
+}
+
+impl Error for JsonError {}
+
+pub fn to_node(fs: &FileSystem) -> JsonNode {
+    fn node(fs: &FileSystem, sizes: &[u64], id: NodeId) -> JsonNode {
+        let name = fs.node(id).name.clone();
+        match &fs.node(id).item {
+            DirectoryItem::File(file) => JsonNode::File {
+                name,
+                size: file.size,
+            },
+            DirectoryItem::Directory(dir) => JsonNode::Dir {
+                name,
+                total_size: Some(sizes[id]),
+                children: dir
+                    .children
+                    .iter()
+                    .map(|&child| node(fs, sizes, child))
+                    .collect(),
+            },
+        }
+    }
+
+    node(fs, &fs.sizes(), FileSystem::ROOT)
+}
+
+pub fn from_node(root: &JsonNode) -> Result<FileSystem, JsonError> {
+    fn add(fs: &mut FileSystem, dir: NodeId, children: &[JsonNode]) -> Result<(), JsonError> {
+        for child in children {
+            let name = match child {
+                JsonNode::File { name, .. } | JsonNode::Dir { name, .. } => name,
+            };
+            if !is_valid_name(name) {
+                return Err(JsonError::Name(name.clone()));
+            }
+            if let Some(existing) = fs.child(dir, name) {
+                return Err(JsonError::Duplicate {
+                    path: fs.path(existing),
+                });
+            }
+            match child {
+                JsonNode::File { size, .. } => {
+                    fs.add(dir, name, DirectoryItem::File(File { size: *size }));
+                }
+                JsonNode::Dir { children, .. } => {
+                    let id = fs.add(dir, name, DirectoryItem::Directory(Directory::default()));
+                    add(fs, id, children)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Compares the totals given in the document to the real ones, both walked in the same order.
+    fn check_totals(
+        fs: &FileSystem,
+        sizes: &[u64],
+        id: NodeId,
+        node: &JsonNode,
+    ) -> Result<(), JsonError> {
+        if let JsonNode::Dir {
+            total_size,
+            children,
+            ..
+        } = node
+        {
+            if let Some(claimed) = *total_size {
+                if claimed != sizes[id] {
+                    return Err(JsonError::TotalSize {
+                        path: fs.path(id),
+                        claimed,
+                        actual: sizes[id],
+                    });
+                }
+            }
+            for (&child, node) in fs.children(id).iter().zip(children) {
+                check_totals(fs, sizes, child, node)?;
+            }
+        }
+        Ok(())
+    }
+
+    let JsonNode::Dir { name, children, .. } = root else {
+        return Err(JsonError::Root);
+    };
+    if name != "/" {
+        return Err(JsonError::Root);
+    }
+    let mut fs = FileSystem::new();
+    add(&mut fs, FileSystem::ROOT, children)?;
+    check_totals(&fs, &fs.sizes(), FileSystem::ROOT, root)?;
+    Ok(fs)
+}
+
+pub fn to_json(fs: &FileSystem) -> String {
+    serde_json::to_string_pretty(&to_node(fs)).expect("The tree is always serialisable")
+}
+
+pub fn from_json(json: &str) -> Result<FileSystem, JsonError> {
+    from_node(&serde_json::from_str(json).map_err(JsonError::Json)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day7::input_generator_part_1;
+
+    const EXAMPLE: &str = include_str!("../../examples/2022/day7/example1.txt");
+    const EXAMPLE_JSON: &str = include_str!("../../examples/2022/day7/example1.json");
+
+    #[test]
+    fn test_to_json() {
+        let fs = input_generator_part_1(EXAMPLE).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&to_json(&fs)).unwrap();
+        let expected: serde_json::Value = serde_json::from_str(EXAMPLE_JSON).unwrap();
+        assert_eq!(json, expected);
+    }
+    #[test]
+    fn test_from_json() {
+        let fs = input_generator_part_1(EXAMPLE).unwrap();
+        assert_eq!(from_json(EXAMPLE_JSON).unwrap(), fs);
+        assert_eq!(from_json(&to_json(&fs)).unwrap(), fs);
+    }
+    #[test]
+    fn test_from_json_without_totals() {
+        let fs = from_json(
+            r#"{"type": "dir", "name": "/", "children": [
+                {"type": "dir", "name": "e", "children": [{"type": "file", "name": "i", "size": 584}]}
+            ]}"#,
+        )
+        .unwrap();
+        assert_eq!(fs.size(FileSystem::ROOT), 584);
+    }
+    #[test]
+    fn test_from_json_errors() {
+        assert!(matches!(
+            from_json(r#"{"type": "file", "name": "/", "size": 1}"#),
+            Err(JsonError::Root)
+        ));
+        assert!(matches!(
+            from_json(r#"{"type": "link", "name": "/"}"#),
+            Err(JsonError::Json(_))
+        ));
+        assert!(matches!(
+            from_json(
+                r#"{"type": "dir", "name": "/", "children": [
+                    {"type": "file", "name": "a", "size": 1},
+                    {"type": "dir", "name": "a"}
+                ]}"#
+            ),
+            Err(JsonError::Duplicate { path }) if path == "/a"
+        ));
+        assert!(matches!(
+            from_json(r#"{"type": "dir", "name": "/", "children": [{"type": "dir", "name": "a/b"}]}"#),
+            Err(JsonError::Name(name)) if name == "a/b"
+        ));
+        assert!(matches!(
+            from_json(r#"{"type": "dir", "name": "/", "children": [{"type": "dir", "name": ".."}]}"#),
+            Err(JsonError::Name(name)) if name == ".."
+        ));
+        assert!(matches!(
+            from_json(r#"{"type": "dir", "name": "/", "children": [{"type": "file", "name": ".", "size": 1}]}"#),
+            Err(JsonError::Name(name)) if name == "."
+        ));
+        assert!(matches!(
+            from_json(
+                r#"{"type": "dir", "name": "/", "children": [
+                    {"type": "dir", "name": "a", "total_size": 2, "children": [
+                        {"type": "file", "name": "b", "size": 1}
+                    ]}
+                ]}"#
+            ),
+            Err(JsonError::TotalSize { path, claimed: 2, actual: 1 }) if path == "/a"
+        ));
+    }
+}
@@ -0,0 +1,272 @@
+//! `find`-style questions about a filesystem, like "the ten largest directories under /a".
+
+use std::{error::Error, fmt};
+
+use super::{FileSystem, NodeId};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Kind {
+    File,
+    Directory,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SortKey {
+    /// The order the tree is walked in, each directory before the things inside it.
+    #[default]
+    Walk,
+    Path,
+    Name,
+    Size,
+}
+
+/// Which nodes to find, and how to order them. Every filter left as `None` lets everything
+/// through, so `Query::default()` finds every node in the tree.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Query {
+    pub kind: Option<Kind>,
+    pub min_size: Option<u64>,
+    pub max_size: Option<u64>,
+    /// A glob matched against the name alone, where `*` is any run of characters and `?` is any
+    /// one character.
+    pub name: Option<String>,
+    /// Depths count from the directory being searched, which is at depth 0, like `find`.
+    pub min_depth: Option<usize>,
+    pub max_depth: Option<usize>,
+    /// Search this directory rather than the root.
+    pub under: Option<String>,
+    pub sort: SortKey,
+    pub descending: bool,
+    pub limit: Option<usize>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Match {
+    pub id: NodeId,
+    pub path: String,
+    /// The total size, for a directory.
+    pub size: u64,
+    pub depth: usize,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum QueryError {
+    NotFound(String),
+    NotADirectory(String),
+}
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QueryError::NotFound(path) => write!(f, "{} does not exist", path),
+            QueryError::NotADirectory(path) => write!(f, "{} is not a directory", path),
+        }
+    }
+}
+
+impl Error for QueryError {}
+
+/// Whether `name` matches `glob` as a whole.
+pub fn glob_match(glob: &str, name: &str) -> bool {
+    let (glob, name): (Vec<char>, Vec<char>) = (glob.chars().collect(), name.chars().collect());
+    // Where to retry from when the last `*` has to swallow one more character
+    let mut backtrack = None;
+    let (mut g, mut n) = (0, 0);
+    while n < name.len() {
+        match glob.get(g) {
+            Some('*') => {
+                backtrack = Some((g, n));
+                g += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                g += 1;
+                n += 1;
+            }
+            _ => match backtrack {
+                Some((star, swallowed)) => {
+                    backtrack = Some((star, swallowed + 1));
+                    g = star + 1;
+                    n = swallowed + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+    glob[g..].iter().all(|&c| c == '*')
+}
+
+impl Query {
+    pub fn run(&self, fs: &FileSystem) -> Result<Vec<Match>, QueryError> {
+        let start = match &self.under {
+            Some(path) => {
+                let id = fs
+                    .lookup(path)
+                    .ok_or_else(|| QueryError::NotFound(path.clone()))?;
+                if !fs.is_dir(id) {
+                    return Err(QueryError::NotADirectory(path.clone()));
+                }
+                id
+            }
+            None => FileSystem::ROOT,
+        };
+        let sizes = fs.sizes();
+
+        let mut matches = vec![];
+        let mut stack = vec![(start, 0)];
+        while let Some((id, depth)) = stack.pop() {
+            stack.extend(
+                fs.children(id)
+                    .iter()
+                    .rev()
+                    .map(|&child| (child, depth + 1)),
+            );
+            let kind = if fs.is_dir(id) {
+                Kind::Directory
+            } else {
+                Kind::File
+            };
+            let size = sizes[id];
+            let keep = self.kind.is_none_or(|wanted| wanted == kind)
+                && self.min_size.is_none_or(|min| size >= min)
+                && self.max_size.is_none_or(|max| size <= max)
+                && self.min_depth.is_none_or(|min| depth >= min)
+                && self.max_depth.is_none_or(|max| depth <= max)
+                && self
+                    .name
+                    .as_ref()
+                    .is_none_or(|glob| glob_match(glob, &fs.node(id).name));
+            if keep {
+                matches.push(Match {
+                    id,
+                    path: fs.path(id),
+                    size,
+                    depth,
+                });
+            }
+        }
+
+        match self.sort {
+            SortKey::Walk => {}
+            SortKey::Path => matches.sort_by(|a, b| a.path.cmp(&b.path)),
+            SortKey::Name => matches.sort_by(|a, b| fs.node(a.id).name.cmp(&fs.node(b.id).name)),
+            SortKey::Size => matches.sort_by_key(|m| m.size),
+        }
+        if self.descending {
+            matches.reverse();
+        }
+        if let Some(limit) = self.limit {
+            matches.truncate(limit);
+        }
+        Ok(matches)
+    }
+}
+
+/// One `<size>\t<path>` line per match.
+pub fn render(matches: &[Match]) -> String {
+    matches
+        .iter()
+        .map(|m| format!("{}\t{}\n", m.size, m.path))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day7::input_generator_part_1;
+
+    const EXAMPLE: &str = include_str!("../../examples/2022/day7/example1.txt");
+
+    fn paths(query: Query) -> Vec<String> {
+        let fs = input_generator_part_1(EXAMPLE).unwrap();
+        query
+            .run(&fs)
+            .unwrap()
+            .into_iter()
+            .map(|m| m.path)
+            .collect()
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("*", ""));
+        assert!(glob_match("*.txt", "b.txt"));
+        assert!(!glob_match("*.txt", "b.txt.dat"));
+        assert!(glob_match("d.*", "d.log"));
+        assert!(glob_match("?", "d"));
+        assert!(!glob_match("?", "d.log"));
+        assert!(glob_match("*a*b*", "xxaxxbxxb"));
+        assert!(!glob_match("*a*b", "xxaxxbxxbc"));
+        assert!(glob_match("h.lst", "h.lst"));
+    }
+    #[test]
+    fn test_everything() {
+        assert_eq!(paths(Query::default()).len(), 14);
+    }
+    #[test]
+    fn test_filters() {
+        assert_eq!(
+            paths(Query {
+                kind: Some(Kind::Directory),
+                max_size: Some(100_000),
+                ..Query::default()
+            }),
+            vec!["/a", "/a/e"]
+        );
+        assert_eq!(
+            paths(Query {
+                kind: Some(Kind::File),
+                name: Some("d.*".to_owned()),
+                ..Query::default()
+            }),
+            vec!["/d/d.log", "/d/d.ext"]
+        );
+        assert_eq!(
+            paths(Query {
+                under: Some("/a".to_owned()),
+                min_depth: Some(1),
+                max_depth: Some(1),
+                min_size: Some(3000),
+                ..Query::default()
+            }),
+            vec!["/a/f", "/a/h.lst"]
+        );
+    }
+    #[test]
+    fn test_sort_and_limit() {
+        assert_eq!(
+            paths(Query {
+                kind: Some(Kind::Directory),
+                sort: SortKey::Size,
+                descending: true,
+                limit: Some(2),
+                ..Query::default()
+            }),
+            vec!["/", "/d"]
+        );
+        assert_eq!(
+            paths(Query {
+                under: Some("/d".to_owned()),
+                kind: Some(Kind::File),
+                sort: SortKey::Name,
+                ..Query::default()
+            }),
+            vec!["/d/d.ext", "/d/d.log", "/d/j", "/d/k"]
+        );
+    }
+    #[test]
+    fn test_under_errors() {
+        let fs = input_generator_part_1(EXAMPLE).unwrap();
+        let under = |path: &str| Query {
+            under: Some(path.to_owned()),
+            ..Query::default()
+        };
+        assert_eq!(
+            under("/x").run(&fs),
+            Err(QueryError::NotFound("/x".to_owned()))
+        );
+        assert_eq!(
+            under("/b.txt").run(&fs),
+            Err(QueryError::NotADirectory("/b.txt".to_owned()))
+        );
+    }
+}
@@ -0,0 +1,198 @@
+//! The indented tree the puzzle uses to describe a filesystem:
+//!
+//! ```text
+//! - / (dir)
+//!   - a (dir)
+//!     - i (file, size=584)
+//! ```
+
+use crate::error::ParseError;
+
+use super::{is_valid_name, Directory, DirectoryItem, File, FileSystem, NodeId};
+
+/// Renders the tree below the root, optionally with each directory's total size as
+/// `(dir, size=N)`.
+pub fn render(fs: &FileSystem, dir_sizes: bool) -> String {
+    let sizes = fs.sizes();
+    let mut tree = String::new();
+    let mut stack = vec![(FileSystem::ROOT, 0)];
+    while let Some((id, depth)) = stack.pop() {
+        let node = fs.node(id);
+        let details = match &node.item {
+            DirectoryItem::File(file) => format!("file, size={}", file.size),
+            DirectoryItem::Directory(_) if dir_sizes => format!("dir, size={}", sizes[id]),
+            DirectoryItem::Directory(_) => "dir".to_owned(),
+        };
+        tree.push_str(&format!(
+            "{}- {} ({})\n",
+            "  ".repeat(depth),
+            node.name,
+            details
+        ));
+        stack.extend(
+            fs.children(id)
+                .iter()
+                .rev()
+                .map(|&child| (child, depth + 1)),
+        );
+    }
+    tree
+}
+
+/// Parses a tree as written by [`render`], checking any directory sizes it gives.
+pub fn parse(input: &str) -> Result<FileSystem, ParseError> {
+    let mut fs = FileSystem::new();
+    // The directory at each depth along the branch the last entry was on
+    let mut branch: Vec<NodeId> = vec![];
+    let mut claimed_sizes = vec![];
+
+    for (i, line) in input.lines().enumerate() {
+        let error = |token: &str, message: &str| ParseError::at(7, i + 1, line, token, message);
+        let entry = line.trim_start_matches(' ');
+        let indent = line.len() - entry.len();
+        if indent % 2 != 0 {
+            return Err(error(entry, "expected two spaces of indent per level"));
+        }
+        let depth = indent / 2;
+        let body = entry
+            .strip_prefix("- ")
+            .ok_or_else(|| error(entry, "expected `- ` before the name"))?;
+        let (name, details) = body.rsplit_once(" (").ok_or_else(|| {
+            ParseError::at_end(7, i + 1, line, "expected `(dir)` or `(file, size=N)`")
+        })?;
+        let details = details
+            .strip_suffix(')')
+            .ok_or_else(|| ParseError::at_end(7, i + 1, line, "expected a closing `)`"))?;
+        let (kind, size) = match details.split_once(", size=") {
+            Some((kind, size)) => (kind, Some(size)),
+            None => (details, None),
+        };
+        let size = size
+            .map(|size| {
+                size.parse::<u64>()
+                    .map(|parsed| (parsed, size))
+                    .map_err(|_| error(size, "expected a size"))
+            })
+            .transpose()?;
+
+        if depth == 0 {
+            if i != 0 || name != "/" || kind != "dir" {
+                return Err(error(
+                    entry,
+                    "expected only the root, `- / (dir)`, at the top",
+                ));
+            }
+            branch.push(FileSystem::ROOT);
+            if let Some((size, token)) = size {
+                claimed_sizes.push((FileSystem::ROOT, i, line, token, size));
+            }
+            continue;
+        }
+        if branch.is_empty() {
+            return Err(error(entry, "expected the root, `- / (dir)`, first"));
+        }
+        if depth > branch.len() {
+            return Err(error(entry, "indented further than the directory above"));
+        }
+        branch.truncate(depth);
+        let parent = *branch.last().expect("The root is never truncated away");
+        if !is_valid_name(name) {
+            return Err(error(
+                name,
+                "expected a name without spaces or `/` that isn't `.` or `..`",
+            ));
+        }
+        if fs.child(parent, name).is_some() {
+            return Err(error(name, "this name is already in the directory"));
+        }
+        match (kind, size) {
+            ("dir", size) => {
+                let id = fs.add(parent, name, DirectoryItem::Directory(Directory::default()));
+                branch.push(id);
+                if let Some((size, token)) = size {
+                    claimed_sizes.push((id, i, line, token, size));
+                }
+            }
+            ("file", Some((size, _))) => {
+                fs.add(parent, name, DirectoryItem::File(File { size }));
+            }
+            ("file", None) => {
+                return Err(ParseError::at(
+                    7,
+                    i + 1,
+                    line,
+                    &details[details.len()..],
+                    "expected the size of the file",
+                ))
+            }
+            (kind, _) => return Err(error(kind, "expected `dir` or `file`")),
+        }
+    }
+    if branch.is_empty() {
+        return Err(ParseError::at_end(
+            7,
+            1,
+            "",
+            "expected the root, `- / (dir)`",
+        ));
+    }
+
+    let sizes = fs.sizes();
+    for (id, i, line, token, size) in claimed_sizes {
+        if sizes[id] != size {
+            return Err(ParseError::at(
+                7,
+                i + 1,
+                line,
+                token,
+                format!("the directory's contents add up to {}", sizes[id]),
+            ));
+        }
+    }
+    Ok(fs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day7;
+
+    const EXAMPLE: &str = include_str!("../../examples/2022/day7/example1.txt");
+    const EXAMPLE_TREE: &str = include_str!("../../examples/2022/day7/example1.tree");
+
+    #[test]
+    fn test_render() {
+        let fs = day7::input_generator_part_1(EXAMPLE).unwrap();
+        assert_eq!(render(&fs, false), EXAMPLE_TREE);
+        assert!(render(&fs, true).starts_with(
+            "- / (dir, size=48381165)\n  - a (dir, size=94853)\n    - e (dir, size=584)\n"
+        ));
+    }
+    #[test]
+    fn test_round_trip() {
+        let fs = parse(EXAMPLE_TREE).unwrap();
+        assert_eq!(render(&fs, false), EXAMPLE_TREE);
+        assert_eq!(parse(&render(&fs, true)).unwrap(), fs);
+    }
+    #[test]
+    fn test_parse_errors() {
+        let error = parse("- / (dir)\n    - a (dir)\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 5));
+        let error = parse("- / (dir)\n  - a (file)\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 12));
+        let error = parse("- / (dir)\n  - a (link)\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 8));
+        let error = parse("- / (dir)\n  - a (dir)\n  - a (file, size=1)\n").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 5));
+        let error = parse("- / (dir, size=2)\n  - a (file, size=1)\n").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 16));
+        assert_eq!(error.message, "the directory's contents add up to 1");
+        let error = parse("  - a (dir)\n").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 3));
+        let error = parse("- / (dir)\n  - my file (file, size=3)\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 5));
+        let error = parse("- / (dir)\n  - .. (dir)\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 5));
+        assert!(parse("").is_err());
+    }
+}
@@ -0,0 +1,88 @@
+use std::{error::Error, fmt};
+
+/// Why a day's input could not be parsed, and where in the input the problem is.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseError {
+    pub day: u8,
+    /// 1-based line number within the input.
+    pub line: usize,
+    /// 1-based column, in characters, within the line.
+    pub column: usize,
+    /// The offending line.
+    pub text: String,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(
+        day: u8,
+        line: usize,
+        column: usize,
+        text: &str,
+        message: impl Into<String>,
+    ) -> ParseError {
+        ParseError {
+            day,
+            line,
+            column,
+            text: text.to_owned(),
+            message: message.into(),
+        }
+    }
+
+    /// An error pointing at `token`, which must be a slice of `text`.
+    pub fn at(
+        day: u8,
+        line: usize,
+        text: &str,
+        token: &str,
+        message: impl Into<String>,
+    ) -> ParseError {
+        let offset = token.as_ptr() as usize - text.as_ptr() as usize;
+        debug_assert!(
+            offset + token.len() <= text.len(),
+            "token is not part of text"
+        );
+        ParseError::new(day, line, text[..offset].chars().count() + 1, text, message)
+    }
+
+    /// An error pointing just past the end of `text`, for when something is missing.
+    pub fn at_end(day: u8, line: usize, text: &str, message: impl Into<String>) -> ParseError {
+        ParseError::new(day, line, text.chars().count() + 1, text, message)
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "day {}, line {}, column {}: {}",
+            self.day, self.line, self.column, self.message
+        )?;
+        writeln!(f, "{:>5} | {}", self.line, self.text)?;
+        write!(f, "{:>5} | {}^", "", " ".repeat(self.column - 1))
+    }
+}
+
+impl Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_at_token() {
+        let line = "move 1 from x to 3";
+        let error = ParseError::at(5, 12, line, &line[12..13], "expected a stack number");
+        assert_eq!(error.column, 13);
+        assert_eq!(error.text, line);
+    }
+    #[test]
+    fn test_display() {
+        let error = ParseError::at_end(4, 3, "2-4", "expected a comma between the two elves");
+        assert_eq!(
+            error.to_string(),
+            "day 4, line 3, column 4: expected a comma between the two elves\n    3 | 2-4\n      |    ^"
+        );
+    }
+}
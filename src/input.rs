@@ -0,0 +1,81 @@
+use std::{
+    fmt, fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+/// Where a day's puzzle input comes from.
+#[derive(Clone, Debug, PartialEq)]
+pub enum InputSource {
+    File(PathBuf),
+    Stdin,
+    Inline(String),
+}
+
+impl InputSource {
+    /// `-` means stdin, anything else is a path.
+    pub fn from_arg(arg: &str) -> InputSource {
+        if arg == "-" {
+            InputSource::Stdin
+        } else {
+            InputSource::File(PathBuf::from(arg))
+        }
+    }
+
+    /// The usual place for a day's input: `<input_dir>/<year>/day<day>.txt`.
+    pub fn default_for(input_dir: &Path, year: u16, day: u8) -> InputSource {
+        InputSource::File(
+            input_dir
+                .join(year.to_string())
+                .join(format!("day{}.txt", day)),
+        )
+    }
+
+    pub fn read(&self) -> io::Result<String> {
+        match self {
+            InputSource::File(path) => fs::read_to_string(path),
+            InputSource::Stdin => {
+                let mut contents = String::new();
+                io::stdin().read_to_string(&mut contents)?;
+                Ok(contents)
+            }
+            InputSource::Inline(contents) => Ok(contents.clone()),
+        }
+    }
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputSource::File(path) => write!(f, "{}", path.display()),
+            InputSource::Stdin => write!(f, "stdin"),
+            InputSource::Inline(_) => write!(f, "inline input"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_arg() {
+        assert_eq!(InputSource::from_arg("-"), InputSource::Stdin);
+        assert_eq!(
+            InputSource::from_arg("other/day1.txt"),
+            InputSource::File(PathBuf::from("other/day1.txt"))
+        );
+    }
+    #[test]
+    fn test_default_for() {
+        assert_eq!(
+            InputSource::default_for(Path::new("input"), 2022, 5),
+            InputSource::File(PathBuf::from("input/2022/day5.txt"))
+        );
+    }
+    #[test]
+    fn test_read_inline() {
+        let source = InputSource::Inline("A Y\nB X\nC Z".to_owned());
+        assert_eq!(source.read().unwrap(), "A Y\nB X\nC Z");
+    }
+}
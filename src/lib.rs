@@ -0,0 +1,51 @@
+//! Advent of Code 2022: each day's parsers, models and solvers, plus the tooling the `aoc`
+//! binary is built from.
+
+pub mod answers;
+pub mod bench;
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod error;
+pub mod input;
+pub mod report;
+pub mod scaffold;
+pub mod solution;
+
+use solution::Day;
+
+/// Every solved day, in order.
+pub const DAYS: &[Day] = &[
+    Day {
+        day: 1,
+        parts: &[&day1::Part1, &day1::Part2],
+    },
+    Day {
+        day: 2,
+        parts: &[&day2::Part1, &day2::Part2],
+    },
+    Day {
+        day: 3,
+        parts: &[&day3::Part1, &day3::Part2],
+    },
+    Day {
+        day: 4,
+        parts: &[&day4::Part1, &day4::Part2],
+    },
+    Day {
+        day: 5,
+        parts: &[&day5::Part1, &day5::Part2],
+    },
+    Day {
+        day: 6,
+        parts: &[&day6::Part1, &day6::Part2],
+    },
+    Day {
+        day: 7,
+        parts: &[&day7::Part1, &day7::Part2],
+    },
+];
@@ -1,17 +1,771 @@
-//mod day1;
-//mod day2;
-//mod day3;
-//mod day4;
-//mod day5;
-//mod day6;
-mod day7;
+use std::{
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process, thread,
+    time::Duration,
+};
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+
+use advent_of_code_2022::{
+    answers::{Answers, Verdict},
+    bench,
+    day5::{self, CraneModel},
+    day7::{
+        self,
+        generate::{self, RandomTree, SizeDistribution},
+        query::{self, Kind, Query, SortKey},
+        Disk, FileSystem,
+    },
+    error::ParseError,
+    input::InputSource,
+    report::{self, RunRecord, Status},
+    scaffold,
+    solution::{Day, Part},
+    DAYS,
+};
+
+#[derive(Parser, Debug)]
+#[command(name = "aoc", about = "Advent of Code 2022 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Args, Debug)]
+struct InputArgs {
+    /// Read the puzzle input from this file, or from stdin if given `-`
+    #[arg(long, conflicts_with = "input_text")]
+    input: Option<String>,
+    /// Use this text as the puzzle input
+    #[arg(long)]
+    input_text: Option<String>,
+    #[command(flatten)]
+    dir: InputDirArgs,
+}
+
+#[derive(Args, Debug)]
+struct InputDirArgs {
+    /// Directory holding the inputs, as `<year>/day<day>.txt`
+    #[arg(long, env = "AOC_INPUT_DIR", default_value = "input")]
+    input_dir: PathBuf,
+    /// Year of the inputs to read from the input directory
+    #[arg(long, env = "AOC_YEAR", default_value_t = 2022)]
+    year: u16,
+}
+
+impl InputArgs {
+    fn source(&self, day: u8) -> InputSource {
+        if let Some(text) = &self.input_text {
+            InputSource::Inline(text.clone())
+        } else if let Some(arg) = &self.input {
+            InputSource::from_arg(arg)
+        } else {
+            InputSource::default_for(&self.dir.input_dir, self.dir.year, day)
+        }
+    }
+}
+
+#[derive(Args, Debug)]
+struct Selection {
+    /// Day to run
+    #[arg(long, required_unless_present = "all", value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: Option<u8>,
+    /// Only run this part of the day
+    #[arg(long, requires = "day", value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+    /// Run every day in turn, each reading its input from the input directory
+    #[arg(long, conflicts_with_all = ["day", "input", "input_text"])]
+    all: bool,
+}
+
+impl Selection {
+    /// The selected days, exiting if the day asked for is not registered.
+    fn days(&self) -> Vec<&'static Day> {
+        if self.all {
+            return DAYS.iter().collect();
+        }
+        let day = self.day.expect("clap requires --day unless --all is given");
+        match DAYS.iter().find(|d| d.day == day) {
+            Some(day) => vec![day],
+            None => {
+                eprintln!("Day {} has not been solved yet", day);
+                process::exit(1);
+            }
+        }
+    }
+
+    /// The selected parts of `day` with their part numbers, exiting if the part asked for
+    /// does not exist.
+    fn parts(&self, day: &Day) -> Vec<(u8, &'static dyn Part)> {
+        match self.part {
+            Some(n) => match day.part(n) {
+                Some(part) => vec![(n, part)],
+                None => {
+                    eprintln!("Day {} has no part {} yet", day.day, n);
+                    process::exit(1);
+                }
+            },
+            None => (1..).zip(day.parts.iter().copied()).collect(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+enum RunFormat {
+    /// The answers, for reading
+    Plain,
+    Json,
+    Csv,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum BenchFormat {
+    Table,
+    Json,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Run the solution for one day, or for every day
+    Run {
+        #[command(flatten)]
+        selection: Selection,
+        #[command(flatten)]
+        input: InputArgs,
+        /// How to print the results of each part
+        #[arg(long, value_enum, default_value_t = RunFormat::Plain)]
+        format: RunFormat,
+    },
+    /// Time the parse and solve phases of each selected part
+    Bench {
+        #[command(flatten)]
+        selection: Selection,
+        #[command(flatten)]
+        input: InputArgs,
+        /// How many times to run each part
+        #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u64).range(1..))]
+        iterations: u64,
+        #[arg(long, value_enum, default_value_t = BenchFormat::Table)]
+        format: BenchFormat,
+    },
+    /// Check every registered day's answers against the recorded answers
+    Verify {
+        /// Only verify this day
+        #[arg(long)]
+        day: Option<u8>,
+        /// Save the answers for parts that have none recorded yet
+        #[arg(long)]
+        record: bool,
+        /// Directory holding the recorded answers, as `<year>.toml`
+        #[arg(long, env = "AOC_ANSWERS_DIR", default_value = "answers")]
+        answers_dir: PathBuf,
+        #[command(flatten)]
+        input: InputDirArgs,
+    },
+    /// Start a new day from the template, with an empty input and example
+    New {
+        /// Day to create
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        #[command(flatten)]
+        input: InputDirArgs,
+    },
+    /// Try day 5 with other cranes
+    Day5 {
+        #[command(subcommand)]
+        command: Day5Command,
+    },
+    /// Explore the filesystem described by a day 7 transcript
+    Day7 {
+        #[command(subcommand)]
+        command: Day7Command,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum Day5Command {
+    /// Print the crates left on top of each stack once a crane has carried out the instructions
+    Run {
+        #[command(flatten)]
+        input: InputArgs,
+        /// `9000` and `9001` for parts 1 and 2, `limited:<capacity>` for one that can only lift
+        /// so many crates at once, or `flipping` for one that flips every other load
+        #[arg(long, default_value = "9000")]
+        crane: CraneModel,
+        /// Check every instruction can be carried out before running any of them
+        #[arg(long)]
+        check: bool,
+    },
+    /// Print the ship after each instruction, or animate it in the terminal
+    Trace {
+        #[command(flatten)]
+        input: InputArgs,
+        /// The crane, as for `aoc day5 run`
+        #[arg(long, default_value = "9000")]
+        crane: CraneModel,
+        /// Check every instruction can be carried out before showing any of them
+        #[arg(long)]
+        check: bool,
+        /// Redraw the ship in place for each instruction instead of printing every step
+        #[arg(long)]
+        animate: bool,
+        /// How long to show each frame of the animation for, in milliseconds
+        #[arg(long, default_value_t = 500, requires = "animate")]
+        delay_ms: u64,
+    },
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum FindType {
+    File,
+    Dir,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum FindSort {
+    /// Each directory before the things inside it
+    Walk,
+    Path,
+    Name,
+    Size,
+}
+
+#[derive(Subcommand, Debug)]
+enum Day7Command {
+    /// List every directory with its total size, like `du`
+    Du {
+        #[command(flatten)]
+        input: InputArgs,
+    },
+    /// List every line of the transcript that contradicts itself or couldn't have happened
+    Check {
+        #[command(flatten)]
+        input: InputArgs,
+    },
+    /// List the files and directories matching every filter given, with their total sizes
+    Find {
+        #[command(flatten)]
+        input: InputArgs,
+        /// Only files, or only directories
+        #[arg(long = "type", value_enum)]
+        kind: Option<FindType>,
+        #[arg(long)]
+        min_size: Option<u64>,
+        #[arg(long)]
+        max_size: Option<u64>,
+        /// Only names matching this glob, where `*` is anything and `?` is any one character
+        #[arg(long)]
+        name: Option<String>,
+        /// Skip anything shallower than this, counting the searched directory as depth 0
+        #[arg(long)]
+        min_depth: Option<usize>,
+        /// Skip anything deeper than this, counting the searched directory as depth 0
+        #[arg(long)]
+        max_depth: Option<usize>,
+        /// Search this directory, like `/a/e`, rather than the root
+        #[arg(long)]
+        under: Option<String>,
+        #[arg(long, value_enum, default_value_t = FindSort::Walk)]
+        sort: FindSort,
+        /// Sort in descending order
+        #[arg(long)]
+        reverse: bool,
+        /// Only list this many, after sorting
+        #[arg(long)]
+        limit: Option<usize>,
+    },
+    /// Show what changed between two transcripts, exiting with 1 if anything did
+    Diff {
+        /// The earlier transcript, or `-` for stdin
+        before: String,
+        /// The later transcript, or `-` for stdin if the earlier one isn't
+        after: String,
+    },
+    /// Print the filesystem as JSON
+    Json {
+        #[command(flatten)]
+        input: InputArgs,
+    },
+    /// Print a transcript exploring the filesystem in a JSON document
+    FromJson {
+        /// The document, as printed by `aoc day7 json`, or `-` for stdin
+        path: String,
+    },
+    /// Print a transcript exploring a directory on disk, or a random tree
+    Generate {
+        /// Explore this directory rather than a random tree
+        #[arg(long, conflicts_with_all = ["seed", "max_depth", "max_fan_out", "dir_chance", "min_size", "max_size", "uniform_sizes"])]
+        from_dir: Option<PathBuf>,
+        /// Seed for the random tree; the same seed always gives the same tree
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+        /// How many directories deep the tree can go
+        #[arg(long)]
+        max_depth: Option<usize>,
+        /// The most entries a directory can have
+        #[arg(long)]
+        max_fan_out: Option<usize>,
+        /// The chance of each entry being a directory, from 0 to 1
+        #[arg(long, value_parser = parse_chance)]
+        dir_chance: Option<f64>,
+        #[arg(long)]
+        min_size: Option<u64>,
+        #[arg(long)]
+        max_size: Option<u64>,
+        /// Pick file sizes evenly between the bounds, rather than evenly across orders of
+        /// magnitude
+        #[arg(long)]
+        uniform_sizes: bool,
+        /// Also write the answers the transcript should give to this file, like an example's
+        /// `.toml`
+        #[arg(long)]
+        answers: Option<PathBuf>,
+    },
+    /// Draw the filesystem as the puzzle does, `- / (dir)` and so on
+    Tree {
+        #[command(flatten)]
+        input: InputArgs,
+        /// Also give each directory's total size
+        #[arg(long)]
+        sizes: bool,
+    },
+}
+
+/// A probability, from 0 to 1.
+fn parse_chance(arg: &str) -> Result<f64, String> {
+    match arg.parse::<f64>() {
+        Ok(chance) if (0.0..=1.0).contains(&chance) => Ok(chance),
+        _ => Err("expected a number from 0 to 1".to_owned()),
+    }
+}
+
+/// Reads the input for each selected day, reporting any that can't be read.
+fn inputs(
+    selection: &Selection,
+    input: &InputArgs,
+    failed: &mut bool,
+) -> Vec<(&'static Day, String)> {
+    selection
+        .days()
+        .into_iter()
+        .filter_map(|day| {
+            let source = input.source(day.day);
+            match source.read() {
+                Ok(contents) => Some((day, contents)),
+                Err(error) => {
+                    eprintln!("error: could not read {}: {}", source, error);
+                    *failed = true;
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+/// Reads an input, exiting if it can't be read.
+fn read_or_exit(source: InputSource) -> String {
+    source.read().unwrap_or_else(|error| {
+        eprintln!("error: could not read {}: {}", source, error);
+        process::exit(1);
+    })
+}
+
+fn exit_unparsable(error: ParseError) -> ! {
+    eprintln!("error: could not parse the input for {}", error);
+    process::exit(1);
+}
+
+/// The day 5 ship and instructions, exiting if they can't be parsed, or with `check` if any
+/// instruction can't be carried out.
+fn day5_input(source: InputSource, check: bool) -> (day5::Ship, day5::InstructionSet) {
+    let (ship, instructions) = day5::input_generator_part_1(&read_or_exit(source))
+        .unwrap_or_else(|error| exit_unparsable(error));
+    if check {
+        if let Err(error) = ship.check(&instructions) {
+            eprintln!("error: {}", error);
+            process::exit(1);
+        }
+    }
+    (ship, instructions)
+}
+
+/// Reads and builds the day 7 filesystem, exiting if that fails.
+fn day7_filesystem(source: InputSource) -> FileSystem {
+    day7::input_generator_part_1(&read_or_exit(source))
+        .unwrap_or_else(|error| exit_unparsable(error))
+}
+
+fn report(error: ParseError, failed: &mut bool) {
+    eprintln!("error: could not parse the input for {}", error);
+    *failed = true;
+}
 
 fn main() {
-    //day1::main();
-    //day2::main();
-    //day3::main();
-    //day4::main();
-    //day5::main();
-    //day6::main();
-    day7::main();
+    let cli = Cli::parse();
+    let mut failed = false;
+    match cli.command {
+        Command::Run {
+            selection,
+            input,
+            format,
+        } => {
+            let mut records = vec![];
+            for day in selection.days() {
+                if selection.all && format == RunFormat::Plain {
+                    println!("Day {}:\n", day.day);
+                }
+                let source = input.source(day.day);
+                let contents = match source.read() {
+                    Ok(contents) => contents,
+                    Err(error) => {
+                        let error = format!("could not read {}: {}", source, error);
+                        eprintln!("error: {}", error);
+                        failed = true;
+                        for (n, _) in selection.parts(day) {
+                            records.push(RunRecord::failed(
+                                day.day,
+                                n,
+                                Status::ReadError,
+                                error.clone(),
+                            ));
+                        }
+                        continue;
+                    }
+                };
+                for (n, part) in selection.parts(day) {
+                    match part.run_timed(&contents) {
+                        Ok(run) => {
+                            if format == RunFormat::Plain {
+                                println!("Part {}:\n\n{}\n\n\n", n, run.answer);
+                            }
+                            records.push(RunRecord::ok(day.day, n, run));
+                        }
+                        Err(error) => {
+                            records.push(RunRecord::failed(
+                                day.day,
+                                n,
+                                Status::ParseError,
+                                error.to_string(),
+                            ));
+                            report(error, &mut failed);
+                        }
+                    }
+                }
+            }
+            match format {
+                RunFormat::Plain => {}
+                RunFormat::Json => println!("{}", report::render_json(&records)),
+                RunFormat::Csv => print!("{}", report::render_csv(&records)),
+            }
+        }
+        Command::Bench {
+            selection,
+            input,
+            iterations,
+            format,
+        } => {
+            let mut results = vec![];
+            for (day, contents) in inputs(&selection, &input, &mut failed) {
+                for (n, part) in selection.parts(day) {
+                    match bench::bench(day.day, n, part, &contents, iterations as usize) {
+                        Ok(result) => results.push(result),
+                        Err(error) => {
+                            report(error, &mut failed);
+                            continue;
+                        }
+                    }
+                }
+            }
+            match format {
+                BenchFormat::Table => print!("{}", bench::render_table(&results)),
+                BenchFormat::Json => println!("{}", bench::render_json(&results)),
+            }
+        }
+        Command::Verify {
+            day,
+            record,
+            answers_dir,
+            input,
+        } => {
+            let path = Answers::path(&answers_dir, input.year);
+            let mut answers = Answers::load(&path).unwrap_or_else(|error| {
+                eprintln!("error: could not load the answers from {}", error);
+                process::exit(1);
+            });
+            if let Some(day) = day.filter(|&n| DAYS.iter().all(|d| d.day != n)) {
+                eprintln!("Day {} has not been solved yet", day);
+                process::exit(1);
+            }
+            let mut recorded = false;
+            for day in DAYS.iter().filter(|d| day.is_none_or(|n| d.day == n)) {
+                let source = InputSource::default_for(&input.input_dir, input.year, day.day);
+                let contents = match source.read() {
+                    Ok(contents) => contents,
+                    Err(error) => {
+                        eprintln!("error: could not read {}: {}", source, error);
+                        failed = true;
+                        continue;
+                    }
+                };
+                for (n, part) in (1..).zip(day.parts.iter()) {
+                    let answer = match part.run(&contents) {
+                        Ok(answer) => answer,
+                        Err(error) => {
+                            report(error, &mut failed);
+                            continue;
+                        }
+                    };
+                    match answers.check(day.day, n, &answer) {
+                        Verdict::Pass => println!("day {} part {}: pass", day.day, n),
+                        Verdict::Fail { expected } => {
+                            println!(
+                                "day {} part {}: FAIL (expected {}, got {})",
+                                day.day, n, expected, answer
+                            );
+                            failed = true;
+                        }
+                        Verdict::Unknown if record => {
+                            println!("day {} part {}: recorded {}", day.day, n, answer);
+                            answers.record(day.day, n, answer);
+                            recorded = true;
+                        }
+                        Verdict::Unknown => {
+                            println!("day {} part {}: unknown (got {})", day.day, n, answer)
+                        }
+                    }
+                }
+            }
+            if recorded {
+                if let Err(error) = answers.save(&path) {
+                    eprintln!("error: could not save {}: {}", path.display(), error);
+                    failed = true;
+                }
+            }
+        }
+        Command::New { day, input } => {
+            match scaffold::new_day(Path::new("."), day, &input.input_dir, input.year) {
+                Ok(changed) => {
+                    for path in changed {
+                        println!("{}", path.display());
+                    }
+                }
+                Err(error) => {
+                    eprintln!("error: could not create day {}: {}", day, error);
+                    failed = true;
+                }
+            }
+        }
+        Command::Day5 { command } => match command {
+            Day5Command::Run {
+                input,
+                crane,
+                check,
+            } => {
+                let input = day5_input(input.source(5), check);
+                match day5::try_solve_with(&input, crane.crane().as_mut()) {
+                    Ok(tops) => println!("{}", tops),
+                    Err(error) => {
+                        eprintln!("error: {}", error);
+                        failed = true;
+                    }
+                }
+            }
+            Day5Command::Trace {
+                input,
+                crane,
+                check,
+                animate,
+                delay_ms,
+            } => {
+                let input = day5_input(input.source(5), check);
+                let (ship, instructions) = &input;
+                let traced = if !animate {
+                    day5::render_trace(&input, crane.crane().as_mut())
+                        .map(|trace| print!("{}", trace))
+                } else {
+                    let delay = Duration::from_millis(delay_ms);
+                    let steps = instructions.instructions.len();
+                    // Clear the screen and go back to the top left before each frame
+                    let frame = |heading: String, ship: &day5::Ship| {
+                        print!("\x1b[2J\x1b[H{}\n\n{}", heading, ship);
+                        io::stdout()
+                            .flush()
+                            .expect("Should be able to write to stdout");
+                        thread::sleep(delay);
+                    };
+                    frame(format!("0/{}", steps), ship);
+                    ship.clone().execute_traced(
+                        instructions,
+                        crane.crane().as_mut(),
+                        |i, ins, ship| frame(format!("{}/{}: {}", i + 1, steps, ins), ship),
+                    )
+                };
+                if let Err(error) = traced {
+                    eprintln!("error: {}", error);
+                    failed = true;
+                }
+            }
+        },
+        Command::Day7 { command } => match command {
+            Day7Command::Du { input } => {
+                print!(
+                    "{}",
+                    day7::render_du(&day7_filesystem(input.source(7)).du())
+                )
+            }
+            Day7Command::Check { input } => {
+                let transcript = read_or_exit(input.source(7));
+                let lines = day7::parse_transcript(&transcript)
+                    .unwrap_or_else(|error| exit_unparsable(error));
+                let (_, issues) = day7::check::check(&lines);
+                for issue in &issues {
+                    println!("{}", issue);
+                }
+                failed = !issues.is_empty();
+            }
+            Day7Command::Diff { before, after } => {
+                if before == "-" && after == "-" {
+                    eprintln!("error: only one of the transcripts can be read from stdin");
+                    process::exit(1);
+                }
+                let before = day7_filesystem(InputSource::from_arg(&before));
+                let after = day7_filesystem(InputSource::from_arg(&after));
+                let diff = day7::diff::diff(&before, &after);
+                print!("{}", day7::diff::render(&diff));
+                failed = diff.change() != day7::diff::Change::Unchanged;
+            }
+            Day7Command::Find {
+                input,
+                kind,
+                min_size,
+                max_size,
+                name,
+                min_depth,
+                max_depth,
+                under,
+                sort,
+                reverse,
+                limit,
+            } => {
+                let query = Query {
+                    kind: kind.map(|kind| match kind {
+                        FindType::File => Kind::File,
+                        FindType::Dir => Kind::Directory,
+                    }),
+                    min_size,
+                    max_size,
+                    name,
+                    min_depth,
+                    max_depth,
+                    under,
+                    sort: match sort {
+                        FindSort::Walk => SortKey::Walk,
+                        FindSort::Path => SortKey::Path,
+                        FindSort::Name => SortKey::Name,
+                        FindSort::Size => SortKey::Size,
+                    },
+                    descending: reverse,
+                    limit,
+                };
+                match query.run(&day7_filesystem(input.source(7))) {
+                    Ok(matches) => print!("{}", query::render(&matches)),
+                    Err(error) => {
+                        eprintln!("error: {}", error);
+                        failed = true;
+                    }
+                }
+            }
+            Day7Command::Json { input } => {
+                println!("{}", day7::json::to_json(&day7_filesystem(input.source(7))))
+            }
+            Day7Command::FromJson { path } => {
+                let source = InputSource::from_arg(&path);
+                let contents = source.read().unwrap_or_else(|error| {
+                    eprintln!("error: could not read {}: {}", source, error);
+                    process::exit(1);
+                });
+                match day7::json::from_json(&contents) {
+                    Ok(fs) => print!("{}", generate::transcript(&fs)),
+                    Err(error) => {
+                        eprintln!("error: could not load {}: {}", source, error);
+                        failed = true;
+                    }
+                }
+            }
+            Day7Command::Generate {
+                from_dir,
+                seed,
+                max_depth,
+                max_fan_out,
+                dir_chance,
+                min_size,
+                max_size,
+                uniform_sizes,
+                answers,
+            } => {
+                let fs = match from_dir {
+                    Some(dir) => generate::from_dir(&dir).unwrap_or_else(|error| {
+                        eprintln!("error: could not read {}: {}", dir.display(), error);
+                        process::exit(1);
+                    }),
+                    None => {
+                        let default = RandomTree::default();
+                        let (default_min, default_max) = match default.sizes {
+                            SizeDistribution::Uniform { min, max }
+                            | SizeDistribution::LogUniform { min, max } => (min, max),
+                        };
+                        let (min, max) = (
+                            min_size.unwrap_or(default_min),
+                            max_size.unwrap_or(default_max),
+                        );
+                        if min > max {
+                            eprintln!("error: --min-size is bigger than --max-size");
+                            process::exit(1);
+                        }
+                        let shape = RandomTree {
+                            max_depth: max_depth.unwrap_or(default.max_depth),
+                            max_fan_out: max_fan_out.unwrap_or(default.max_fan_out),
+                            dir_chance: dir_chance.unwrap_or(default.dir_chance),
+                            sizes: if uniform_sizes {
+                                SizeDistribution::Uniform { min, max }
+                            } else {
+                                SizeDistribution::LogUniform { min, max }
+                            },
+                        };
+                        if shape.max_total().is_none() {
+                            eprintln!(
+                                "error: a tree that big could hold more bytes than can be \
+                                 counted; lower --max-size, --max-fan-out or --max-depth"
+                            );
+                            process::exit(1);
+                        }
+                        generate::random(shape, seed)
+                    }
+                };
+                print!("{}", generate::transcript(&fs));
+                if let Some(path) = answers {
+                    let (part1, part2) = generate::expected_answers(&fs, Disk::default());
+                    let mut toml = format!("part1 = \"{}\"\n", part1);
+                    if let Some(part2) = part2 {
+                        toml.push_str(&format!("part2 = \"{}\"\n", part2));
+                    }
+                    if let Err(error) = fs::write(&path, toml) {
+                        eprintln!("error: could not write {}: {}", path.display(), error);
+                        failed = true;
+                    }
+                }
+            }
+            Day7Command::Tree { input, sizes } => {
+                print!(
+                    "{}",
+                    day7::tree::render(&day7_filesystem(input.source(7)), sizes)
+                )
+            }
+        },
+    }
+    if failed {
+        process::exit(1);
+    }
 }
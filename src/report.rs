@@ -0,0 +1,164 @@
+use std::time::Duration;
+
+use serde::{Serialize, Serializer};
+
+use crate::solution::Run;
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    Ok,
+    ReadError,
+    ParseError,
+}
+
+impl Status {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Status::Ok => "ok",
+            Status::ReadError => "read_error",
+            Status::ParseError => "parse_error",
+        }
+    }
+}
+
+/// The outcome of running one part, in a form scripts can consume.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct RunRecord {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Option<String>,
+    #[serde(rename = "parse_time_ns", serialize_with = "as_nanos")]
+    pub parse_time: Option<Duration>,
+    #[serde(rename = "solve_time_ns", serialize_with = "as_nanos")]
+    pub solve_time: Option<Duration>,
+    pub status: Status,
+    pub error: Option<String>,
+}
+
+fn as_nanos<S: Serializer>(duration: &Option<Duration>, serializer: S) -> Result<S::Ok, S::Error> {
+    match duration {
+        Some(duration) => serializer.serialize_some(&duration.as_nanos()),
+        None => serializer.serialize_none(),
+    }
+}
+
+impl RunRecord {
+    pub fn ok(day: u8, part: u8, run: Run) -> RunRecord {
+        RunRecord {
+            day,
+            part,
+            answer: Some(run.answer),
+            parse_time: Some(run.parse_time),
+            solve_time: Some(run.solve_time),
+            status: Status::Ok,
+            error: None,
+        }
+    }
+
+    pub fn failed(day: u8, part: u8, status: Status, error: String) -> RunRecord {
+        RunRecord {
+            day,
+            part,
+            answer: None,
+            parse_time: None,
+            solve_time: None,
+            status,
+            error: Some(error),
+        }
+    }
+}
+
+pub fn render_json(records: &[RunRecord]) -> String {
+    serde_json::to_string_pretty(records).expect("Records are always serialisable")
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+pub fn render_csv(records: &[RunRecord]) -> String {
+    let mut csv = String::from("day,part,answer,parse_time_ns,solve_time_ns,status,error\n");
+    for record in records {
+        let nanos = |time: Option<Duration>| time.map(|t| t.as_nanos().to_string());
+        let fields = [
+            record.day.to_string(),
+            record.part.to_string(),
+            record.answer.clone().unwrap_or_default(),
+            nanos(record.parse_time).unwrap_or_default(),
+            nanos(record.solve_time).unwrap_or_default(),
+            record.status.as_str().to_owned(),
+            record.error.clone().unwrap_or_default(),
+        ];
+        let fields: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+        csv.push_str(&fields.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records() -> Vec<RunRecord> {
+        vec![
+            RunRecord::ok(
+                5,
+                1,
+                Run {
+                    answer: "CMZ".to_owned(),
+                    parse_time: Duration::from_nanos(1500),
+                    solve_time: Duration::from_nanos(20),
+                },
+            ),
+            RunRecord::failed(
+                4,
+                2,
+                Status::ParseError,
+                "day 4, line 1: expected a comma, \"here\"".to_owned(),
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_render_csv() {
+        assert_eq!(
+            render_csv(&records()),
+            "day,part,answer,parse_time_ns,solve_time_ns,status,error\n\
+             5,1,CMZ,1500,20,ok,\n\
+             4,2,,,,parse_error,\"day 4, line 1: expected a comma, \"\"here\"\"\"\n"
+        );
+    }
+    #[test]
+    fn test_render_json() {
+        let json: serde_json::Value = serde_json::from_str(&render_json(&records())).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!([
+                {
+                    "day": 5,
+                    "part": 1,
+                    "answer": "CMZ",
+                    "parse_time_ns": 1500,
+                    "solve_time_ns": 20,
+                    "status": "ok",
+                    "error": null,
+                },
+                {
+                    "day": 4,
+                    "part": 2,
+                    "answer": null,
+                    "parse_time_ns": null,
+                    "solve_time_ns": null,
+                    "status": "parse_error",
+                    "error": "day 4, line 1: expected a comma, \"here\"",
+                },
+            ])
+        );
+    }
+}
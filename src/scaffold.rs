@@ -0,0 +1,202 @@
+use std::{
+    error::Error,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+const TEMPLATE: &str = include_str!("template.rs");
+
+/// The file that declares the day modules and lists them in `DAYS`.
+const REGISTRY: &str = "src/lib.rs";
+
+#[derive(Debug)]
+pub enum ScaffoldError {
+    Exists(PathBuf),
+    Io(PathBuf, io::Error),
+    /// The registry file doesn't look the way `register` expects.
+    Registry(&'static str),
+}
+
+impl fmt::Display for ScaffoldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScaffoldError::Exists(path) => write!(f, "{} already exists", path.display()),
+            ScaffoldError::Io(path, error) => write!(f, "{}: {}", path.display(), error),
+            ScaffoldError::Registry(message) => write!(f, "{}: {}", REGISTRY, message),
+        }
+    }
+}
+
+impl Error for ScaffoldError {}
+
+pub fn render_template(day: u8, year: u16) -> String {
+    TEMPLATE
+        .replace("2022/dayn", &format!("{}/day{}", year, day))
+        .replace("DAY_NUMBER", &day.to_string())
+}
+
+/// Adds `pub mod day<day>;` after the last day module and an entry for it at the end of `DAYS`.
+pub fn register(source: &str, day: u8) -> Result<String, ScaffoldError> {
+    let mut lines: Vec<String> = source.lines().map(String::from).collect();
+
+    let last_mod = lines
+        .iter()
+        .rposition(|line| line.starts_with("pub mod day") && line.ends_with(';'))
+        .ok_or(ScaffoldError::Registry("no `pub mod dayN;` lines found"))?;
+    lines.insert(last_mod + 1, format!("pub mod day{};", day));
+
+    let days_start = lines
+        .iter()
+        .position(|line| line.starts_with("pub const DAYS: &[Day] = &["))
+        .ok_or(ScaffoldError::Registry("no `DAYS` list found"))?;
+    let days_end = lines[days_start..]
+        .iter()
+        .position(|line| line == "];")
+        .ok_or(ScaffoldError::Registry("the `DAYS` list is never closed"))?
+        + days_start;
+    let entry = [
+        "    Day {".to_owned(),
+        format!("        day: {},", day),
+        format!("        parts: &[&day{0}::Part1, &day{0}::Part2],", day),
+        "    },".to_owned(),
+    ];
+    lines.splice(days_end..days_end, entry);
+
+    Ok(lines.join("\n") + "\n")
+}
+
+fn write_new(path: &Path, contents: &str) -> Result<(), ScaffoldError> {
+    if path.exists() {
+        return Err(ScaffoldError::Exists(path.to_owned()));
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|error| ScaffoldError::Io(parent.to_owned(), error))?;
+    }
+    fs::write(path, contents).map_err(|error| ScaffoldError::Io(path.to_owned(), error))
+}
+
+/// Creates `src/day<day>.rs` from the template, registers it, and creates an empty input and
+/// example (with answers to fill in) for it, all under `root`. Returns the files created or
+/// changed.
+pub fn new_day(
+    root: &Path,
+    day: u8,
+    input_dir: &Path,
+    year: u16,
+) -> Result<Vec<PathBuf>, ScaffoldError> {
+    let module = root.join(format!("src/day{}.rs", day));
+    if module.exists() {
+        return Err(ScaffoldError::Exists(module));
+    }
+    let registry = root.join(REGISTRY);
+    let source = fs::read_to_string(&registry)
+        .map_err(|error| ScaffoldError::Io(registry.clone(), error))?;
+    let source = register(&source, day)?;
+
+    let input = root
+        .join(input_dir)
+        .join(year.to_string())
+        .join(format!("day{}.txt", day));
+    let example = root.join(format!("examples/{}/day{}/example1.txt", year, day));
+    let example_answers = example.with_extension("toml");
+
+    write_new(&module, &render_template(day, year))?;
+    fs::write(&registry, source).map_err(|error| ScaffoldError::Io(registry.clone(), error))?;
+    let mut changed = vec![module, registry];
+    let files = [
+        (input, ""),
+        (example, ""),
+        (example_answers, "# part1 = \"\"\n# part2 = \"\"\n"),
+    ];
+    for (path, contents) in files {
+        // Don't clobber an input that was downloaded before the day was started
+        if !path.exists() {
+            write_new(&path, contents)?;
+            changed.push(path);
+        }
+    }
+    Ok(changed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REGISTRY_SOURCE: &str = "pub mod day1;
+#[allow(dead_code)]
+pub mod day2;
+pub mod solution;
+
+pub const DAYS: &[Day] = &[
+    Day {
+        day: 1,
+        parts: &[&day1::Part1, &day1::Part2],
+    },
+];
+
+";
+
+    #[test]
+    fn test_render_template() {
+        let rendered = render_template(12, 2022);
+        assert!(rendered.contains("ParseError::at_end(12, i + 1"));
+        assert!(rendered.contains("include_str!(\"../examples/2022/day12/example1.txt\")"));
+        assert!(!rendered.contains("DAY_NUMBER"));
+        assert!(rendered.contains("pub fn input_generator_part_1("));
+        assert!(rendered.contains("pub fn solve_part2("));
+    }
+    #[test]
+    fn test_register() {
+        assert_eq!(
+            register(REGISTRY_SOURCE, 3).unwrap(),
+            "pub mod day1;
+#[allow(dead_code)]
+pub mod day2;
+pub mod day3;
+pub mod solution;
+
+pub const DAYS: &[Day] = &[
+    Day {
+        day: 1,
+        parts: &[&day1::Part1, &day1::Part2],
+    },
+    Day {
+        day: 3,
+        parts: &[&day3::Part1, &day3::Part2],
+    },
+];
+
+"
+        );
+    }
+    #[test]
+    fn test_register_without_registry() {
+        assert!(matches!(
+            register("pub mod day1;\n", 2),
+            Err(ScaffoldError::Registry(_))
+        ));
+    }
+    #[test]
+    fn test_new_day() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join(REGISTRY), REGISTRY_SOURCE).unwrap();
+
+        let changed = new_day(&root, 8, Path::new("input"), 2022).unwrap();
+        assert_eq!(changed.len(), 5);
+        assert!(fs::read_to_string(root.join(REGISTRY))
+            .unwrap()
+            .contains("pub mod day8;"));
+        assert_eq!(
+            fs::read_to_string(root.join("input/2022/day8.txt")).unwrap(),
+            ""
+        );
+        assert!(root.join("examples/2022/day8/example1.txt").exists());
+        assert!(root.join("examples/2022/day8/example1.toml").exists());
+        assert!(matches!(
+            new_day(&root, 8, Path::new("input"), 2022),
+            Err(ScaffoldError::Exists(_))
+        ));
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,64 @@
+use std::{
+    fmt::Display,
+    time::{Duration, Instant},
+};
+
+use crate::error::ParseError;
+
+/// One part of a day's puzzle: how to turn the raw input into something to work on, and how to
+/// get the answer from that.
+pub trait Solution {
+    type Input;
+    type Answer: Display;
+
+    /// Also rejects anything `solve` couldn't cope with, as `solve` can't fail, so the time
+    /// those checks take counts as parse time.
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn solve(input: &Self::Input) -> Self::Answer;
+}
+
+/// Object safe form of [`Solution`], so that parts with different `Input` and `Answer` types can
+/// sit side by side in the registry.
+pub trait Part {
+    fn run_timed(&self, input: &str) -> Result<Run, ParseError>;
+
+    fn run(&self, input: &str) -> Result<String, ParseError> {
+        Ok(self.run_timed(input)?.answer)
+    }
+}
+
+impl<S: Solution> Part for S {
+    fn run_timed(&self, input: &str) -> Result<Run, ParseError> {
+        let start = Instant::now();
+        let parsed = S::parse(input)?;
+        let parse_time = start.elapsed();
+        let start = Instant::now();
+        let answer = S::solve(&parsed);
+        let solve_time = start.elapsed();
+        Ok(Run {
+            answer: answer.to_string(),
+            parse_time,
+            solve_time,
+        })
+    }
+}
+
+/// The answer from running a part, and how long each phase took.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Run {
+    pub answer: String,
+    pub parse_time: Duration,
+    pub solve_time: Duration,
+}
+
+/// A registered day, with its parts in order.
+pub struct Day {
+    pub day: u8,
+    pub parts: &'static [&'static dyn Part],
+}
+
+impl Day {
+    pub fn part(&self, part: u8) -> Option<&'static dyn Part> {
+        self.parts.get(usize::from(part).checked_sub(1)?).copied()
+    }
+}
@@ -1,54 +1,87 @@
+use crate::{error::ParseError, solution::Solution};
+
 #[derive(Clone, Debug, PartialEq)]
-struct Placeholder {}
+pub struct Placeholder {}
 
-fn input_generator_part_1(input: &str) -> Vec<Placeholder> {
-    todo!()
+pub fn input_generator_part_1(input: &str) -> Result<Vec<Placeholder>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            if line.is_empty() {
+                Err(ParseError::at_end(DAY_NUMBER, i + 1, line, "expected something"))
+            } else {
+                Ok(Placeholder {})
+            }
+        })
+        .collect()
 }
 
-fn input_generator_part_2(input: &str) -> Vec<Placeholder> {}
+pub fn input_generator_part_2(input: &str) -> Result<Vec<Placeholder>, ParseError> {
+    input_generator_part_1(input)
+}
 
-fn solve_part1(input: &[Placeholder]) -> u32 {
-    todo!()
+pub fn solve_part1(_input: &[Placeholder]) -> u32 {
+    0
 }
-fn solve_part2(input: &[Placeholder]) -> u32 {
-    todo!()
+pub fn solve_part2(_input: &[Placeholder]) -> u32 {
+    0
 }
 
-pub fn main() {
-    let contents =
-        fs::read_to_string("input/2022/dayn.txt").expect("Should have been able to read the file");
-    let input_part_1 = input_generator_part_1(contents.as_str());
-    let input_part_2 = input_generator_part_2(contents.as_str());
-    let part1_answer = solve_part1(input_part_1);
-    println!("Part 1:\n\n{}\n\n\n", part1_answer);
-    let part2_answer = solve_part2(input_part_2);
-    println!("Part 2:\n\n{}\n\n\n", part2_answer);
+pub struct Part1;
+
+impl Solution for Part1 {
+    type Input = Vec<Placeholder>;
+    type Answer = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input_generator_part_1(input)
+    }
+    fn solve(input: &Self::Input) -> Self::Answer {
+        solve_part1(input)
+    }
+}
+
+pub struct Part2;
+
+impl Solution for Part2 {
+    type Input = Vec<Placeholder>;
+    type Answer = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input_generator_part_2(input)
+    }
+    fn solve(input: &Self::Input) -> Self::Answer {
+        solve_part2(input)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const EXAMPLE: &str = include_str!("../examples/2022/dayn/example1.txt");
+
     #[test]
     fn test_input_generator() {
-        let input = input_generator_part_1("DATA");
-        assert_eq!(input, []);
+        let input = input_generator_part_1(EXAMPLE).unwrap();
+        assert_eq!(input, Vec::<Placeholder>::new());
     }
     #[test]
     fn test_part_1_solver() {
-        let input = input_generator_part_1("DATA");
+        let input = input_generator_part_1(EXAMPLE).unwrap();
         let output = solve_part1(&input);
-        assert_eq!(output, "ANSWER");
+        assert_eq!(output, 0);
     }
     #[test]
     fn test_input_generator_part_2() {
-        let input = input_generator_part_2("DATA");
-        assert_eq!(input, []);
+        let input = input_generator_part_2(EXAMPLE).unwrap();
+        assert_eq!(input, Vec::<Placeholder>::new());
     }
     #[test]
     fn test_part_2_solver() {
-        let input = input_generator_part_2("DATA");
+        let input = input_generator_part_2(EXAMPLE).unwrap();
         let output = solve_part2(&input);
-        assert_eq!(output, "ANSWER");
+        assert_eq!(output, 0);
     }
 }
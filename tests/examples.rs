@@ -0,0 +1,117 @@
+//! Runs every registered day against the puzzle examples in `examples/<year>/day<day>/`.
+//!
+//! Each example is a `<name>.txt` input, with the answers it should give in `<name>.toml`:
+//!
+//! ```toml
+//! part1 = "24000"
+//! part2 = "45000"
+//! ```
+//!
+//! An example without a `.toml` file has no answers to check yet.
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use advent_of_code_2022::DAYS;
+
+#[derive(Clone, Debug, PartialEq)]
+struct Fixture {
+    path: PathBuf,
+    input: String,
+    /// Expected answer for each part number.
+    answers: BTreeMap<u8, String>,
+}
+
+fn load(examples_dir: &Path, year: u16, day: u8) -> Vec<Fixture> {
+    let dir = examples_dir
+        .join(year.to_string())
+        .join(format!("day{}", day));
+    let Ok(entries) = fs::read_dir(&dir) else {
+        return vec![];
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .map(|entry| entry.expect("Should be able to list the examples").path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "txt"))
+        .collect();
+    paths.sort();
+
+    paths
+        .into_iter()
+        .map(|path| {
+            let input = fs::read_to_string(&path).expect("Should be able to read the example");
+            let answers = match fs::read_to_string(path.with_extension("toml")) {
+                Ok(answers) => toml::from_str::<BTreeMap<String, String>>(&answers)
+                    .unwrap_or_else(|error| panic!("{}: {}", path.display(), error))
+                    .into_iter()
+                    .map(|(key, answer)| {
+                        let part = key
+                            .strip_prefix("part")
+                            .and_then(|part| part.parse().ok())
+                            .unwrap_or_else(|| {
+                                panic!("{}: expected `partN`, found `{}`", path.display(), key)
+                            });
+                        (part, answer)
+                    })
+                    .collect(),
+                Err(_) => BTreeMap::new(),
+            };
+            Fixture {
+                path,
+                input,
+                answers,
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn test_every_day_against_its_examples() {
+    let mut checked = 0;
+    let mut failures = vec![];
+    for day in DAYS {
+        for fixture in load(Path::new("examples"), 2022, day.day) {
+            for (&n, expected) in &fixture.answers {
+                let Some(part) = day.part(n) else {
+                    failures.push(format!(
+                        "{}: day {} has no part {}",
+                        fixture.path.display(),
+                        day.day,
+                        n
+                    ));
+                    continue;
+                };
+                match part.run(&fixture.input) {
+                    Ok(answer) if answer == *expected => {}
+                    Ok(answer) => failures.push(format!(
+                        "{}: part {} expected {}, got {}",
+                        fixture.path.display(),
+                        n,
+                        expected,
+                        answer
+                    )),
+                    Err(error) => {
+                        failures.push(format!("{}: part {}: {}", fixture.path.display(), n, error))
+                    }
+                }
+                checked += 1;
+            }
+        }
+    }
+    assert!(checked > 0, "Should have found some examples to check");
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+#[test]
+fn test_load() {
+    let fixtures = load(Path::new("examples"), 2022, 6);
+    assert_eq!(fixtures.len(), 5);
+    assert_eq!(fixtures[0].input, "mjqjpqmgbljsphdztnvjfqwrcgsmlb\n");
+    assert_eq!(
+        fixtures[0].answers,
+        BTreeMap::from([(1, "7".to_owned()), (2, "19".to_owned())])
+    );
+    assert!(load(Path::new("examples"), 2022, 25).is_empty());
+}